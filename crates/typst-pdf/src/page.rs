@@ -17,7 +17,8 @@ use typst::model::Destination;
 use typst::text::{Font, TextItem};
 use typst::util::Numeric;
 use typst::visualize::{
-    FixedStroke, Geometry, Image, LineCap, LineJoin, Paint, Path, PathItem, Shape,
+    FillRule, FixedStroke, Geometry, Image, LineCap, LineJoin, Paint, Path, PathItem,
+    Shape,
 };
 
 use crate::color::PaintEncode;
@@ -166,11 +167,18 @@ fn write_page(ctx: &mut PdfContext, i: usize) {
     }
 
     let mut annotations = page_writer.annotations();
-    for (dest, rect) in &page.links {
+    for (dest, rects) in &page.links {
         let mut annotation = annotations.push();
-        annotation.subtype(AnnotationType::Link).rect(*rect);
+        annotation.subtype(AnnotationType::Link).rect(bounds(rects));
         annotation.border(0.0, 0.0, 0.0, None);
 
+        // A link made of multiple areas only reacts within them.
+        if rects.len() > 1 {
+            annotation.quad_points(rects.iter().flat_map(|rect| {
+                [rect.x1, rect.y2, rect.x2, rect.y2, rect.x2, rect.y1, rect.x1, rect.y1]
+            }));
+        }
+
         let pos = match dest {
             Destination::Url(uri) => {
                 annotation
@@ -261,8 +269,9 @@ pub struct Page {
     pub content: Vec<u8>,
     /// Whether the page uses opacities.
     pub uses_opacities: bool,
-    /// Links in the PDF coordinate system.
-    pub links: Vec<(Destination, Rect)>,
+    /// Links in the PDF coordinate system, each covering one or multiple
+    /// adjacent areas.
+    pub links: Vec<(Destination, Vec<Rect>)>,
     /// The page's PDF label.
     pub label: Option<PdfPageLabel>,
     /// The page's used resources
@@ -334,7 +343,7 @@ pub struct PageContext<'a, 'b> {
     saves: Vec<State>,
     bottom: f32,
     uses_opacities: bool,
-    links: Vec<(Destination, Rect)>,
+    links: Vec<(Destination, Vec<Rect>)>,
     /// Keep track of the resources being used in the page.
     pub resources: HashMap<PageResource, usize>,
 }
@@ -517,11 +526,15 @@ impl PageContext<'_, '_> {
                 line_join,
                 dash_pattern,
                 miter_limit,
+                hairline,
             } = stroke;
 
             paint.set_as_stroke(self, transforms);
 
-            self.content.set_line_width(thickness.to_f32());
+            // A line width of zero is the thinnest line the device can render,
+            // that is, one device pixel.
+            let width = if *hairline { 0.0 } else { thickness.to_f32() };
+            self.content.set_line_width(width);
             if self.state.stroke.as_ref().map(|s| &s.line_cap) != Some(line_cap) {
                 self.content.set_line_cap(to_pdf_line_cap(*line_cap));
             }
@@ -529,7 +542,7 @@ impl PageContext<'_, '_> {
                 self.content.set_line_join(to_pdf_line_join(*line_join));
             }
             if self.state.stroke.as_ref().map(|s| &s.dash_pattern) != Some(dash_pattern) {
-                if let Some(pattern) = dash_pattern {
+                if let Some(pattern) = dash_pattern.as_ref().filter(|p| !p.is_solid()) {
                     self.content.set_dash_pattern(
                         pattern.array.iter().map(|l| l.to_f32()),
                         pattern.phase.to_f32(),
@@ -573,6 +586,7 @@ fn write_frame(ctx: &mut PageContext, frame: &Frame) {
                 Meta::Elem(_) => {}
                 Meta::Hide => {}
                 Meta::PageNumbering(_) => {}
+                Meta::HoverFill(_) => {}
                 Meta::PdfPageLabel(label) => ctx.label = Some(label.clone()),
             },
         }
@@ -711,13 +725,18 @@ fn write_shape(ctx: &mut PageContext, pos: Point, shape: &Shape) {
         Geometry::Path(ref path) => {
             write_path(ctx, x, y, path);
         }
+        Geometry::Polygon(ref points) => {
+            write_path(ctx, x, y, &Path::polygon(points));
+        }
     }
 
-    match (&shape.fill, stroke) {
-        (None, None) => unreachable!(),
-        (Some(_), None) => ctx.content.fill_nonzero(),
-        (None, Some(_)) => ctx.content.stroke(),
-        (Some(_), Some(_)) => ctx.content.fill_nonzero_and_stroke(),
+    match (&shape.fill, stroke, shape.fill_rule) {
+        (None, None, _) => unreachable!(),
+        (Some(_), None, FillRule::NonZero) => ctx.content.fill_nonzero(),
+        (Some(_), None, FillRule::EvenOdd) => ctx.content.fill_even_odd(),
+        (None, Some(_), _) => ctx.content.stroke(),
+        (Some(_), Some(_), FillRule::NonZero) => ctx.content.fill_nonzero_and_stroke(),
+        (Some(_), Some(_), FillRule::EvenOdd) => ctx.content.fill_even_odd_and_stroke(),
     };
 }
 
@@ -804,7 +823,34 @@ fn write_link(ctx: &mut PageContext, pos: Point, dest: &Destination, size: Size)
     let y2 = min_y.to_f32();
     let rect = Rect::new(x1, y1, x2, y2);
 
-    ctx.links.push((dest.clone(), rect));
+    // Extend the previous link if it leads to the same destination and its
+    // last area touches this one from above, like the strips that make up a
+    // link on a round shape do. This way, it becomes a single annotation.
+    if let Some((prev, rects)) = ctx.links.last_mut() {
+        let last = rects.last().unwrap();
+        if prev == dest
+            && (last.y2 - rect.y1).abs() < 1e-3
+            && last.x1 < rect.x2
+            && rect.x1 < last.x2
+        {
+            rects.push(rect);
+            return;
+        }
+    }
+
+    ctx.links.push((dest.clone(), vec![rect]));
+}
+
+/// The bounding box of multiple rectangles in the PDF coordinate system.
+fn bounds(rects: &[Rect]) -> Rect {
+    rects.iter().skip(1).fold(rects[0], |bounds, rect| {
+        Rect::new(
+            bounds.x1.min(rect.x1),
+            bounds.y1.max(rect.y1),
+            bounds.x2.max(rect.x2),
+            bounds.y2.min(rect.y2),
+        )
+    })
 }
 
 fn to_pdf_line_cap(cap: LineCap) -> LineCapStyle {
@@ -832,3 +878,33 @@ fn to_pdf_numbering_style(style: PdfPageLabelStyle) -> NumberingStyle {
         PdfPageLabelStyle::UpperAlpha => NumberingStyle::UpperAlpha,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::layout::{Abs, Frame, FrameItem, Point, Size};
+    use typst::model::Document;
+    use typst::syntax::Span;
+    use typst::visualize::{DashPattern, FixedStroke, Geometry};
+
+    use super::*;
+
+    #[test]
+    fn test_gapless_dash_pattern_is_solid() {
+        let content = |dash_pattern| {
+            let size = Size::splat(Abs::pt(10.0));
+            let stroke = FixedStroke { dash_pattern, ..FixedStroke::default() };
+            let shape = Geometry::Rect(size).stroked(stroke);
+            let mut frame = Frame::soft(size);
+            frame.push(Point::zero(), FrameItem::Shape(shape, Span::detached()));
+            let document = Document::default();
+            let mut ctx = PdfContext::new(&document);
+            let (_, page) = construct_page(&mut ctx, &frame);
+            String::from_utf8(page.content).unwrap()
+        };
+
+        let gapless = DashPattern { array: vec![Abs::zero(); 2], phase: Abs::pt(1.0) };
+        let solid = content(None);
+        assert!(solid.contains("\n[] 0 d\n"), "{solid}");
+        assert_eq!(content(Some(gapless)), solid);
+    }
+}
@@ -15,8 +15,8 @@ use typst::layout::{
 };
 use typst::text::{Font, TextItem};
 use typst::visualize::{
-    Color, FixedStroke, Geometry, Gradient, Image, ImageKind, LineCap, LineJoin, Paint,
-    Path, PathItem, Pattern, RasterFormat, RelativeTo, Shape,
+    Color, FillRule, FixedStroke, Geometry, Gradient, Image, ImageKind, LineCap,
+    LineJoin, Paint, Path, PathItem, Pattern, RasterFormat, RelativeTo, Shape,
 };
 use usvg::{NodeExt, TreeParsing};
 
@@ -166,6 +166,7 @@ fn render_frame(canvas: &mut sk::Pixmap, state: State, frame: &Frame) {
                 Meta::Elem(_) => {}
                 Meta::PageNumbering(_) => {}
                 Meta::PdfPageLabel(_) => {}
+                Meta::HoverFill(_) => {}
                 Meta::Hide => {}
             },
         }
@@ -546,6 +547,7 @@ fn render_shape(canvas: &mut sk::Pixmap, state: State, shape: &Shape) -> Option<
             sk::PathBuilder::from_rect(rect)
         }
         Geometry::Path(ref path) => convert_path(path)?,
+        Geometry::Polygon(ref points) => convert_path(&Path::polygon(points))?,
     };
 
     if let Some(fill) = &shape.fill {
@@ -564,7 +566,10 @@ fn render_shape(canvas: &mut sk::Pixmap, state: State, shape: &Shape) -> Option<
             paint.anti_alias = false;
         }
 
-        let rule = sk::FillRule::default();
+        let rule = match shape.fill_rule {
+            FillRule::NonZero => sk::FillRule::Winding,
+            FillRule::EvenOdd => sk::FillRule::EvenOdd,
+        };
         canvas.fill_path(&path, &paint, rule, ts, state.mask);
     }
 
@@ -575,9 +580,18 @@ fn render_shape(canvas: &mut sk::Pixmap, state: State, shape: &Shape) -> Option<
         line_join,
         dash_pattern,
         miter_limit,
+        hairline,
     }) = &shape.stroke
     {
-        let width = thickness.to_f32();
+        let mut width = thickness.to_f32();
+
+        // Make a hairline exactly one device pixel wide.
+        if *hairline {
+            let scale = (ts.sx * ts.sy - ts.kx * ts.ky).abs().sqrt();
+            if scale > 0.0 {
+                width = 1.0 / scale;
+            }
+        }
 
         // Don't draw zero-pt stroke.
         if width > 0.0 {
@@ -941,6 +955,13 @@ fn to_sk_paint<'a>(
                     .container_transform
                     .post_concat(state.transform.invert().unwrap()),
             };
+            // The mapping onto a stroke's bounding box only applies if the
+            // gradient is relative to the stroked shape itself.
+            let gradient_map = match relative {
+                RelativeTo::Self_ => gradient_map,
+                RelativeTo::Parent => None,
+            };
+
             let width = (container_size.x.to_f32() * state.pixel_per_pt).ceil() as u32;
             let height = (container_size.y.to_f32() * state.pixel_per_pt).ceil() as u32;
 
@@ -14,8 +14,9 @@ use typst::layout::{
 use typst::text::{Font, TextItem};
 use typst::util::hash128;
 use typst::visualize::{
-    Color, FixedStroke, Geometry, Gradient, Image, ImageFormat, LineCap, LineJoin, Paint,
-    Path, PathItem, Pattern, RasterFormat, RatioOrAngle, RelativeTo, Shape, VectorFormat,
+    Color, FillRule, FixedStroke, Geometry, Gradient, Image, ImageFormat, LineCap,
+    LineJoin, Paint, Path, PathItem, Pattern, RasterFormat, RatioOrAngle, RelativeTo,
+    Shape, VectorFormat,
 };
 use xmlwriter::XmlWriter;
 
@@ -25,6 +26,11 @@ use xmlwriter::XmlWriter;
 const CONIC_SEGMENT: usize = 360;
 
 /// Export a frame into a SVG file.
+///
+/// The frame doesn't need to be a page. Any frame, like the one of a single
+/// laid out shape, can be exported on its own. Shapes keep their exact
+/// outline, including rounded corners and outsets, because their geometry
+/// is written as rectangles and paths.
 #[tracing::instrument(skip_all)]
 pub fn svg(frame: &Frame) -> String {
     let mut renderer = SVGRenderer::new();
@@ -490,6 +496,11 @@ impl SVGRenderer {
             self.xml.write_attribute("fill", "none");
         }
 
+        // SVG fills with the non-zero rule by default.
+        if shape.fill_rule == FillRule::EvenOdd {
+            self.xml.write_attribute("fill-rule", "evenodd");
+        }
+
         if let Some(stroke) = &shape.stroke {
             self.write_stroke(
                 stroke,
@@ -647,7 +658,14 @@ impl SVGRenderer {
             }
         }
 
-        self.xml.write_attribute("stroke-width", &stroke.thickness.to_pt());
+        // A non-scaling stroke's width is measured in screen pixels, so a
+        // hairline stays one pixel wide at any zoom level.
+        if stroke.hairline {
+            self.xml.write_attribute("stroke-width", "1");
+            self.xml.write_attribute("vector-effect", "non-scaling-stroke");
+        } else {
+            self.xml.write_attribute("stroke-width", &stroke.thickness.to_pt());
+        }
         self.xml.write_attribute(
             "stroke-linecap",
             match stroke.line_cap {
@@ -666,7 +684,7 @@ impl SVGRenderer {
         );
         self.xml
             .write_attribute("stroke-miterlimit", &stroke.miter_limit.get());
-        if let Some(pattern) = &stroke.dash_pattern {
+        if let Some(pattern) = stroke.dash_pattern.as_ref().filter(|p| !p.is_solid()) {
             self.xml.write_attribute("stroke-dashoffset", &pattern.phase.to_pt());
             self.xml.write_attribute(
                 "stroke-dasharray",
@@ -1181,6 +1199,7 @@ fn convert_geometry_to_path(geometry: &Geometry) -> EcoString {
             builder.rect(x, y);
         }
         Geometry::Path(p) => return convert_path(p),
+        Geometry::Polygon(points) => return convert_path(&Path::polygon(points)),
     };
     builder.0
 }
@@ -1493,3 +1512,28 @@ impl ColorEncode for Color {
 fn correct_pattern_pos(x: f32) -> f32 {
     (x + 0.5) / 2.0
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::syntax::Span;
+    use typst::visualize::DashPattern;
+
+    use super::*;
+
+    #[test]
+    fn test_gapless_dash_pattern_is_solid() {
+        let export = |dash_pattern| {
+            let size = Size::splat(Abs::pt(10.0));
+            let stroke = FixedStroke { dash_pattern, ..FixedStroke::default() };
+            let shape = Geometry::Rect(size).stroked(stroke);
+            let mut frame = Frame::soft(size);
+            frame.push(Point::zero(), FrameItem::Shape(shape, Span::detached()));
+            svg(&frame)
+        };
+
+        let gapless = DashPattern { array: vec![Abs::zero(); 2], phase: Abs::pt(1.0) };
+        let solid = export(None);
+        assert!(!solid.contains("stroke-dash"), "{solid}");
+        assert_eq!(export(Some(gapless)), solid);
+    }
+}
@@ -13,7 +13,7 @@ use crate::diag::{SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
     elem, func, scope, ty, Dict, Element, FromValue, Guard, IntoValue, Label,
-    NativeElement, Recipe, Repr, Selector, Str, Style, Styles, Value,
+    NativeElement, Recipe, Repr, Selector, Smart, Str, Style, Styles, Value,
 };
 use crate::introspection::{Location, Meta, MetaElem};
 use crate::layout::{Align, AlignElem, Axes, Length, MoveElem, PadElem, Rel, Sides};
@@ -21,6 +21,7 @@ use crate::model::{Destination, EmphElem, StrongElem};
 use crate::syntax::Span;
 use crate::text::UnderlineElem;
 use crate::util::fat;
+use crate::visualize::{Fill, Paint, RectElem, Stroke};
 
 /// A piece of document content.
 ///
@@ -511,6 +512,35 @@ impl Content {
     pub fn moved(self, delta: Axes<Rel<Length>>) -> Self {
         MoveElem::new(self).with_dx(delta.x).with_dy(delta.y).pack()
     }
+
+    /// Put this content into a rectangle with the given fill, for example to
+    /// give it a background.
+    ///
+    /// If the content already is a rectangle, its fill is replaced instead, so
+    /// that `filled` and `stroked` can be chained without nesting rectangles.
+    pub fn filled(self, fill: Paint) -> Self {
+        let mut rect = self.into_rect();
+        rect.push_fill(Smart::Custom(Some(Fill::Paint(fill))));
+        rect.pack()
+    }
+
+    /// Put this content into a rectangle with the given stroke on all sides.
+    ///
+    /// Like with [`filled`](Self::filled), a rectangle is restroked instead of
+    /// being wrapped again.
+    pub fn stroked(self, stroke: Stroke) -> Self {
+        let mut rect = self.into_rect();
+        rect.push_stroke(Smart::Custom(Sides::splat(Some(Some(stroke))).into()));
+        rect.pack()
+    }
+
+    /// This content as a rectangle, wrapping it in one if necessary.
+    fn into_rect(self) -> RectElem {
+        match self.to::<RectElem>() {
+            Some(rect) => rect.clone(),
+            None => RectElem::new().with_body(Some(self)),
+        }
+    }
 }
 
 #[scope]
@@ -791,3 +821,27 @@ fn missing_field_no_default(field: &str) -> EcoString {
         field.repr()
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::StyleChain;
+    use crate::text::TextElem;
+    use crate::visualize::Color;
+
+    #[test]
+    fn test_filled_and_stroked_share_one_rect() {
+        let styles = StyleChain::default();
+        let content = TextElem::packed("Hi").filled(Color::RED.into()).stroked(Stroke {
+            paint: Smart::Custom(Color::BLUE.into()),
+            ..Default::default()
+        });
+        let rect = content.to::<RectElem>().unwrap();
+        assert_eq!(
+            rect.fill(styles),
+            Smart::Custom(Some(Fill::Paint(Color::RED.into())))
+        );
+        assert!(rect.stroke(styles).is_custom());
+        assert!(rect.body(styles).unwrap().is::<TextElem>());
+    }
+}
@@ -5,6 +5,7 @@ use ecow::{eco_format, EcoString};
 use crate::diag::StrResult;
 use crate::foundations::{IntoValue, Type, Value, Version};
 use crate::layout::{Align, Length, Rel};
+use crate::util::Numeric;
 use crate::visualize::Stroke;
 
 /// Try to access a field on a value.
@@ -36,13 +37,26 @@ pub(crate) fn field(value: &Value, field: &str) -> StrResult<Value> {
             if let Some(stroke) = dynamic.downcast::<Stroke>() {
                 match field {
                     "paint" => stroke.paint.clone().into_value(),
-                    "thickness" => stroke.thickness.into_value(),
+                    "thickness" => stroke
+                        .full_thickness()
+                        .map(|thickness| {
+                            if thickness.rel.is_zero() {
+                                thickness.abs.into_value()
+                            } else if thickness.abs.is_zero() {
+                                thickness.rel.into_value()
+                            } else {
+                                thickness.into_value()
+                            }
+                        })
+                        .into_value(),
                     "cap" => stroke.line_cap.into_value(),
                     "join" => stroke.line_join.into_value(),
                     "dash" => stroke.dash_pattern.clone().into_value(),
                     "miter-limit" => {
                         stroke.miter_limit.map(|limit| limit.get()).into_value()
                     }
+                    "align" => stroke.align.into_value(),
+                    "hairline" => stroke.hairline.into_value(),
                     _ => return missing(),
                 }
             } else if let Some(align) = dynamic.downcast::<Align>() {
@@ -82,7 +96,7 @@ pub fn fields_on(ty: Type) -> &'static [&'static str] {
     } else if ty == Type::of::<Rel>() {
         &["ratio", "length"]
     } else if ty == Type::of::<Stroke>() {
-        &["paint", "thickness", "cap", "join", "dash", "miter-limit"]
+        &["paint", "thickness", "cap", "join", "dash", "miter-limit", "align", "hairline"]
     } else if ty == Type::of::<Align>() {
         &["x", "y"]
     } else {
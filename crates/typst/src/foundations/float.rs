@@ -4,6 +4,7 @@ use ecow::{eco_format, EcoString};
 
 use crate::foundations::{cast, func, repr, scope, ty, Repr, Str};
 use crate::layout::Ratio;
+use crate::util::Scalar;
 
 /// A floating-point number.
 ///
@@ -70,6 +71,12 @@ cast! {
     v: f64 => Self(v),
 }
 
+cast! {
+    Scalar,
+    self => self.get().into_value(),
+    v: f64 => Self::new(v),
+}
+
 fn parse_float(s: EcoString) -> Result<f64, ParseFloatError> {
     s.replace(repr::MINUS_SIGN, "-").parse()
 }
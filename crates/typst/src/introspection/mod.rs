@@ -31,6 +31,7 @@ use crate::foundations::{
 };
 use crate::layout::PdfPageLabel;
 use crate::model::{Destination, Numbering};
+use crate::visualize::Paint;
 
 /// Interactions between document parts.
 ///
@@ -83,6 +84,9 @@ pub enum Meta {
     PageNumbering(Option<Numbering>),
     /// A PDF page label of the current page.
     PdfPageLabel(PdfPageLabel),
+    /// An alternative fill for the shape below this metadata, which
+    /// interactive exports can show while the pointer hovers over it.
+    HoverFill(Paint),
     /// Indicates that content should be hidden. This variant doesn't appear
     /// in the final frames as it is removed alongside the content that should
     /// be hidden.
@@ -100,6 +104,7 @@ impl Debug for Meta {
             Self::Elem(content) => write!(f, "Elem({:?})", content.func()),
             Self::PageNumbering(value) => write!(f, "PageNumbering({value:?})"),
             Self::PdfPageLabel(label) => write!(f, "PdfPageLabel({label:?})"),
+            Self::HoverFill(paint) => write!(f, "HoverFill({paint:?})"),
             Self::Hide => f.pad("Hide"),
         }
     }
@@ -4,11 +4,11 @@ use crate::foundations::{
     cast, elem, AutoValue, Content, NativeElement, Resolve, Smart, StyleChain, Value,
 };
 use crate::layout::{
-    Abs, Axes, Corners, Em, Fr, Fragment, FrameKind, Layout, Length, Ratio, Regions, Rel,
-    Sides, Size, Spacing, VElem,
+    Abs, Axes, Corners, Em, Fr, Fragment, Frame, FrameKind, Layout, Length, Ratio,
+    Regions, Rel, Sides, Size, Spacing, VElem,
 };
 use crate::util::Numeric;
-use crate::visualize::{clip_rect, Paint, Stroke};
+use crate::visualize::{clip_rect, Paint, Radius, Stroke};
 
 /// An inline-level container that sizes content.
 ///
@@ -67,7 +67,7 @@ pub struct BoxElem {
     /// [rectangle's documentation]($rect.radius) for more details.
     #[resolve]
     #[fold]
-    pub radius: Corners<Option<Rel<Length>>>,
+    pub radius: Corners<Option<Radius<Rel<Length>>>>,
 
     /// How much to pad the box's content.
     ///
@@ -154,7 +154,11 @@ impl Layout for BoxElem {
 
         // Prepare fill and stroke.
         let fill = self.fill(styles);
-        let stroke = self.stroke(styles).map(|s| s.map(Stroke::unwrap_or_default));
+        let stroke = self.stroke(styles);
+        for stroke in stroke.iter().flatten() {
+            stroke.ensure_absolute(self.span())?;
+        }
+        let stroke = stroke.map(|s| s.map(Stroke::unwrap_or_default));
 
         // Clip the contents
         if self.clip(styles) {
@@ -266,7 +270,7 @@ pub struct BlockElem {
     /// [rectangle's documentation]($rect.radius) for more details.
     #[resolve]
     #[fold]
-    pub radius: Corners<Option<Rel<Length>>>,
+    pub radius: Corners<Option<Radius<Rel<Length>>>>,
 
     /// How much to pad the block's content. See the
     /// [box's documentation]($box.inset) for more details.
@@ -356,61 +360,18 @@ impl Layout for BlockElem {
             body = body.clone().padded(inset.map(|side| side.map(Length::from)));
         }
 
-        // Resolve the sizing to a concrete size.
-        let sizing = Axes::new(self.width(styles), self.height(styles));
-        let mut expand = sizing.as_ref().map(Smart::is_custom);
-        let mut size = sizing
-            .resolve(styles)
-            .zip_map(regions.base(), |s, b| s.map(|v| v.relative_to(b)))
-            .unwrap_or(regions.base());
-
         // Layout the child.
+        let sizing = Axes::new(self.width(styles), self.height(styles));
         let mut frames = if self.breakable(styles) {
-            // Measure to ensure frames for all regions have the same width.
-            if sizing.x == Smart::Auto {
-                let pod = Regions::one(size, Axes::splat(false));
-                let frame = body.measure(engine, styles, pod)?.into_frame();
-                size.x = frame.width();
-                expand.x = true;
-            }
-
-            let mut pod = regions;
-            pod.size.x = size.x;
-            pod.expand = expand;
-
-            if expand.y {
-                pod.full = size.y;
-            }
-
-            // Generate backlog for fixed height.
-            let mut heights = vec![];
-            if sizing.y.is_custom() {
-                let mut remaining = size.y;
-                for region in regions.iter() {
-                    let limited = region.y.min(remaining);
-                    heights.push(limited);
-                    remaining -= limited;
-                    if Abs::zero().fits(remaining) {
-                        break;
-                    }
-                }
-
-                if let Some(last) = heights.last_mut() {
-                    *last += remaining;
-                }
-
-                pod.size.y = heights[0];
-                pod.backlog = &heights[1..];
-                pod.last = None;
-            }
-
-            let mut frames = body.layout(engine, styles, pod)?.into_frames();
-            for (frame, &height) in frames.iter_mut().zip(&heights) {
-                *frame.size_mut() =
-                    expand.select(Size::new(size.x, height), frame.size());
-            }
-            frames
+            layout_breakable(engine, styles, regions, &body, sizing)?
         } else {
+            // Resolve the sizing to a concrete size.
+            let expand = sizing.as_ref().map(Smart::is_custom);
+            let size = sizing
+                .resolve(styles)
+                .zip_map(regions.base(), |s, b| s.map(|v| v.relative_to(b)))
+                .unwrap_or(regions.base());
+
             let pod = Regions::one(size, expand);
             let mut frames = body.layout(engine, styles, pod)?.into_frames();
             *frames[0].size_mut() = expand.select(size, frames[0].size());
@@ -419,7 +380,11 @@ impl Layout for BlockElem {
 
         // Prepare fill and stroke.
         let fill = self.fill(styles);
-        let stroke = self.stroke(styles).map(|s| s.map(Stroke::unwrap_or_default));
+        let stroke = self.stroke(styles);
+        for stroke in stroke.iter().flatten() {
+            stroke.ensure_absolute(self.span())?;
+        }
+        let stroke = stroke.map(|s| s.map(Stroke::unwrap_or_default));
 
         // Clip the contents
         if self.clip(styles) {
@@ -461,6 +426,71 @@ impl Layout for BlockElem {
     }
 }
 
+/// Lay out the body of a breakable block or rectangle into the given regions.
+/// The body must already be padded by the container's inset.
+///
+/// All resulting frames have the same width. If the height is fixed, it is
+/// distributed across the regions.
+pub(crate) fn layout_breakable(
+    engine: &mut Engine,
+    styles: StyleChain,
+    regions: Regions,
+    body: &Content,
+    sizing: Axes<Smart<Rel<Length>>>,
+) -> SourceResult<Vec<Frame>> {
+    // Resolve the sizing to a concrete size.
+    let mut expand = sizing.as_ref().map(Smart::is_custom);
+    let mut size = sizing
+        .resolve(styles)
+        .zip_map(regions.base(), |s, b| s.map(|v| v.relative_to(b)))
+        .unwrap_or(regions.base());
+
+    // Measure to ensure frames for all regions have the same width.
+    if sizing.x == Smart::Auto {
+        let pod = Regions::one(size, Axes::splat(false));
+        let frame = body.measure(engine, styles, pod)?.into_frame();
+        size.x = frame.width();
+        expand.x = true;
+    }
+
+    let mut pod = regions;
+    pod.size.x = size.x;
+    pod.expand = expand;
+
+    if expand.y {
+        pod.full = size.y;
+    }
+
+    // Generate backlog for fixed height.
+    let mut heights = vec![];
+    if sizing.y.is_custom() {
+        let mut remaining = size.y;
+        for region in regions.iter() {
+            let limited = region.y.min(remaining);
+            heights.push(limited);
+            remaining -= limited;
+            if Abs::zero().fits(remaining) {
+                break;
+            }
+        }
+
+        if let Some(last) = heights.last_mut() {
+            *last += remaining;
+        }
+
+        pod.size.y = heights[0];
+        pod.backlog = &heights[1..];
+        pod.last = None;
+    }
+
+    let mut frames = body.layout(engine, styles, pod)?.into_frames();
+    for (frame, &height) in frames.iter_mut().zip(&heights) {
+        *frame.size_mut() = expand.select(Size::new(size.x, height), frame.size());
+    }
+
+    Ok(frames)
+}
+
 /// Defines how to size a grid cell along an axis.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Sizing {
@@ -4,7 +4,7 @@ use crate::diag::StrResult;
 use crate::foundations::{
     CastInfo, Dict, Fold, FromValue, IntoValue, Reflect, Resolve, StyleChain, Value,
 };
-use crate::layout::Side;
+use crate::layout::{Abs, Rel, Side, Size};
 use crate::util::Get;
 
 /// A container with components for the four corners of a rectangle.
@@ -52,6 +52,16 @@ impl<T> Corners<T> {
         }
     }
 
+    /// Convert from `&Corners<T>` to `Corners<&T>`.
+    pub fn as_ref(&self) -> Corners<&T> {
+        Corners {
+            top_left: &self.top_left,
+            top_right: &self.top_right,
+            bottom_right: &self.bottom_right,
+            bottom_left: &self.bottom_left,
+        }
+    }
+
     /// Zip two instances into one.
     pub fn zip<U>(self, other: Corners<U>) -> Corners<(T, U)> {
         Corners {
@@ -80,6 +90,13 @@ impl<T> Corners<T> {
     }
 }
 
+impl Corners<Rel<Abs>> {
+    /// Evaluate the corners relative to the shorter side of the given `size`.
+    pub fn relative_to(self, size: Size) -> Corners<Abs> {
+        self.map(|corner| corner.relative_to(size.min_by_side()))
+    }
+}
+
 impl<T> Get<Corner> for Corners<T> {
     type Component = T;
 
@@ -163,7 +180,7 @@ impl<T> FromValue for Corners<Option<T>>
 where
     T: FromValue + Clone,
 {
-    fn from_value(mut value: Value) -> StrResult<Self> {
+    fn from_value(value: Value) -> StrResult<Self> {
         let keys = [
             "top-left",
             "top-right",
@@ -176,8 +193,10 @@ where
             "rest",
         ];
 
-        if let Value::Dict(dict) = &mut value {
-            if dict.iter().any(|(key, _)| keys.contains(&key.as_str())) {
+        match value {
+            Value::Dict(mut dict)
+                if dict.iter().any(|(key, _)| keys.contains(&key.as_str())) =>
+            {
                 let mut take = |key| dict.take(key).ok().map(T::from_value).transpose();
                 let rest = take("rest")?;
                 let left = take("left")?.or_else(|| rest.clone());
@@ -200,14 +219,11 @@ where
                 };
 
                 dict.finish(&keys)?;
-                return Ok(corners);
+                Ok(corners)
             }
-        }
-
-        if T::castable(&value) {
-            Ok(Self::splat(Some(T::from_value(value)?)))
-        } else {
-            Err(Self::error(&value))
+            // A single value, like `4pt`, applies to all four corners.
+            value if T::castable(&value) => Ok(Self::splat(Some(T::from_value(value)?))),
+            value => Err(Self::error(&value)),
         }
     }
 }
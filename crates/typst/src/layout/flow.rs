@@ -12,8 +12,8 @@ use crate::layout::{
 use crate::model::{FootnoteElem, FootnoteEntry, ParElem};
 use crate::util::Numeric;
 use crate::visualize::{
-    CircleElem, EllipseElem, ImageElem, LineElem, PathElem, PolygonElem, RectElem,
-    SquareElem,
+    BasicShape, CircleElem, EllipseElem, ImageElem, LineElem, PathElem, PolygonElem,
+    RectElem, SquareElem,
 };
 
 /// Arranges spacing, paragraphs and block-level elements into a flow.
@@ -56,7 +56,7 @@ impl Layout for FlowElem {
             } else if let Some(elem) = child.to::<ParElem>() {
                 layouter.layout_par(engine, elem, styles)?;
             } else if child.is::<LineElem>()
-                || child.is::<RectElem>()
+                || child.to::<RectElem>().is_some_and(|rect| !rect.breakable(styles))
                 || child.is::<SquareElem>()
                 || child.is::<EllipseElem>()
                 || child.is::<CircleElem>()
@@ -65,7 +65,8 @@ impl Layout for FlowElem {
                 || child.is::<PathElem>()
             {
                 let layoutable = child.with::<dyn Layout>().unwrap();
-                layouter.layout_single(engine, layoutable, styles)?;
+                let shape = child.with::<dyn BasicShape>();
+                layouter.layout_single(engine, layoutable, styles, shape)?;
             } else if child.is::<MetaElem>() {
                 let mut frame = Frame::soft(Size::zero());
                 frame.meta(styles, true);
@@ -108,6 +109,8 @@ struct FlowLayouter<'a> {
     initial: Size,
     /// Whether the last block was a paragraph.
     last_was_par: bool,
+    /// Whether the last block was a shape with a shifted baseline.
+    last_was_shifted: bool,
     /// Spacing and layouted blocks for the current region.
     items: Vec<FlowItem>,
     /// A queue of floats.
@@ -178,6 +181,7 @@ impl<'a> FlowLayouter<'a> {
             expand,
             initial: regions.size,
             last_was_par: false,
+            last_was_shifted: false,
             items: vec![],
             pending_floats: vec![],
             has_footnotes: false,
@@ -262,6 +266,7 @@ impl<'a> FlowLayouter<'a> {
         }
 
         self.last_was_par = true;
+        self.last_was_shifted = false;
         Ok(())
     }
 
@@ -272,16 +277,26 @@ impl<'a> FlowLayouter<'a> {
         engine: &mut Engine,
         content: &dyn Layout,
         styles: StyleChain,
+        shape: Option<&dyn BasicShape>,
     ) -> SourceResult<()> {
         let align = AlignElem::alignment_in(styles).resolve(styles);
         let sticky = BlockElem::sticky_in(styles);
-        let pod = Regions::one(self.regions.base(), Axes::splat(false));
+        // Pass on the remaining height to a shape with a fractional height,
+        // so that it can fill it. Relative sizes still resolve against the
+        // full region.
+        let mut pod = Regions::one(self.regions.base(), Axes::splat(false));
+        if shape.is_some_and(|shape| shape.sizing(styles).y.is_fractional()) {
+            pod.size.y = self.regions.size.y;
+        }
         let frame = content.layout(engine, styles, pod)?.into_frame();
         self.layout_item(
             engine,
             FlowItem::Frame { frame, align, sticky, movable: true },
         )?;
         self.last_was_par = false;
+        self.last_was_shifted = shape.is_some_and(|shape| {
+            !matches!(shape.baseline(styles), Smart::Custom(shift) if shift.is_zero())
+        });
         Ok(())
     }
 
@@ -359,6 +374,7 @@ impl<'a> FlowLayouter<'a> {
         self.root = is_root;
         self.regions.root = false;
         self.last_was_par = false;
+        self.last_was_shifted = false;
 
         Ok(())
     }
@@ -509,6 +525,18 @@ impl<'a> FlowLayouter<'a> {
             size.y = self.initial.y;
         }
 
+        // A flow with a single block that is a shape with a shifted baseline,
+        // like the body of a box holding just such a shape, takes on that
+        // shape's baseline.
+        let single = !self.root
+            && self.last_was_shifted
+            && self
+                .items
+                .iter()
+                .filter(|item| matches!(item, FlowItem::Frame { .. }))
+                .count()
+                == 1;
+
         let mut output = Frame::soft(size);
         let mut ruler = FixedAlign::Start;
         let mut float_top_offset = Abs::zero();
@@ -532,6 +560,9 @@ impl<'a> FlowLayouter<'a> {
                     let y = offset + ruler.position(size.y - used.y);
                     let pos = Point::new(x, y);
                     offset += frame.height();
+                    if single && frame.has_baseline() {
+                        output.set_baseline(y + frame.baseline());
+                    }
                     output.push_frame(pos, frame);
                 }
                 FlowItem::Placed { frame, x_align, y_align, delta, float, .. } => {
@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use ecow::{eco_format, EcoString};
 
+use crate::diag::{bail, SourceResult};
 use crate::foundations::{cast, dict, Dict, Repr, StyleChain, Value};
 use crate::introspection::{Meta, MetaElem};
 use crate::layout::{
@@ -15,7 +16,8 @@ use crate::syntax::Span;
 use crate::text::TextItem;
 use crate::util::Numeric;
 use crate::visualize::{
-    ellipse, styled_rect, Color, FixedStroke, Geometry, Image, Paint, Path, Shape,
+    ellipse, outline_items, outset_area, styled_rect, Color, FillRule, FixedStroke,
+    Geometry, Image, Paint, Path, Radius, Shape,
 };
 
 /// A finished layout with items at fixed positions.
@@ -137,6 +139,19 @@ impl Frame {
     pub fn items(&self) -> std::slice::Iter<'_, (Point, FrameItem)> {
         self.items.iter()
     }
+
+    /// An iterator over the shapes directly inside this frame alongside their
+    /// positions relative to the top-left of the frame.
+    ///
+    /// The position is the origin of the shape's geometry. Shapes in nested
+    /// groups are not included; they can be found by descending into
+    /// [`FrameItem::Group`] items and applying the group's transform.
+    pub fn shapes(&self) -> impl Iterator<Item = (Point, &Shape)> {
+        self.items.iter().filter_map(|(pos, item)| match item {
+            FrameItem::Shape(shape, _) => Some((*pos, shape)),
+            _ => None,
+        })
+    }
 }
 
 /// Insert items and subframes.
@@ -164,6 +179,44 @@ impl Frame {
         }
     }
 
+    /// Add a shape with the given geometry, fill, and stroke at a position in
+    /// the foreground.
+    ///
+    /// The position is the top-left corner of the geometry's coordinate
+    /// system. Rectangles have sharp corners and can have a different stroke
+    /// on each side. For all other geometries, the stroke must be the same on
+    /// all sides. Use [`fill_and_stroke`](Self::fill_and_stroke) for a
+    /// rectangle with a radius or outset.
+    pub fn push_shape(
+        &mut self,
+        pos: Point,
+        geometry: Geometry,
+        fill: Option<Paint>,
+        stroke: Sides<Option<FixedStroke>>,
+        span: Span,
+    ) -> SourceResult<()> {
+        let items = match geometry {
+            Geometry::Rect(size) => {
+                let radius = Corners::splat(Radius::default());
+                outline_items(styled_rect(size, radius, fill, stroke), pos, size, span)
+            }
+            _ if !stroke.is_uniform() => {
+                bail!(span, "only rectangles can have a different stroke on each side");
+            }
+            geometry => {
+                let shape = Shape {
+                    geometry,
+                    fill,
+                    stroke: stroke.top,
+                    fill_rule: FillRule::default(),
+                };
+                vec![(pos, FrameItem::Shape(shape, span))]
+            }
+        };
+        Arc::make_mut(&mut self.items).extend(items);
+        Ok(())
+    }
+
     /// Add zero-sized metadata at the origin.
     pub fn push_positionless_meta(&mut self, meta: Meta) {
         self.push(Point::zero(), FrameItem::Meta(meta, Size::zero()));
@@ -178,6 +231,10 @@ impl Frame {
     }
 
     /// Add an item at a position in the background.
+    ///
+    /// Like for all other items, the position is relative to the top-left
+    /// corner of the frame, with the y-axis pointing downwards. The item ends
+    /// up below all existing items.
     pub fn prepend(&mut self, pos: Point, item: FrameItem) {
         self.insert(0, pos, item);
     }
@@ -317,17 +374,12 @@ impl Frame {
         fill: Option<Paint>,
         stroke: Sides<Option<FixedStroke>>,
         outset: Sides<Rel<Abs>>,
-        radius: Corners<Rel<Abs>>,
+        radius: Corners<Radius<Rel<Abs>>>,
         span: Span,
     ) {
-        let outset = outset.relative_to(self.size());
-        let size = self.size() + outset.sum_by_axis();
-        let pos = Point::new(-outset.left, -outset.top);
-        self.prepend_multiple(
-            styled_rect(size, radius, fill, stroke)
-                .into_iter()
-                .map(|x| (pos, FrameItem::Shape(x, span))),
-        )
+        let (pos, size) = outset_area(self.size(), outset);
+        let shapes = styled_rect(size, radius, fill, stroke);
+        self.prepend_multiple(outline_items(shapes, pos, size, span))
     }
 
     /// Arbitrarily transform the contents of the frame.
@@ -337,6 +389,33 @@ impl Frame {
         }
     }
 
+    /// Transform the frame around its center and resize it to the bounding box
+    /// of the transformed frame.
+    ///
+    /// In contrast to [`transform`](Self::transform), the frame's size follows
+    /// the transformed contents. The baseline is reset.
+    pub fn transform_bounded(&mut self, transform: Transform) {
+        let center = (self.size / 2.0).to_point();
+        let ts = Transform::translate(center.x, center.y)
+            .pre_concat(transform)
+            .pre_concat(Transform::translate(-center.x, -center.y));
+
+        let corners = [
+            Point::zero(),
+            Point::with_x(self.size.x),
+            self.size.to_point(),
+            Point::with_y(self.size.y),
+        ]
+        .map(|point| point.transform(ts));
+        let min = corners.iter().fold(corners[0], |min, &p| min.min(p));
+        let max = corners.iter().fold(corners[0], |max, &p| max.max(p));
+
+        self.transform(ts);
+        self.translate(-min);
+        self.size = (max - min).to_size();
+        self.baseline = None;
+    }
+
     /// Clip the contents of a frame to a clip path.
     ///
     /// The clip path can be the size of the frame in the case of a
@@ -585,3 +664,70 @@ pub enum PdfPageLabelStyle {
     /// `AA` to `ZZ` and so on for the next).
     UpperAlpha,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{Angle, Ratio};
+
+    #[test]
+    fn test_transform_bounded_encloses_contents() {
+        let size = Size::new(Abs::pt(40.0), Abs::pt(20.0));
+        let corners = [
+            Point::zero(),
+            Point::with_x(size.x),
+            size.to_point(),
+            Point::with_y(size.y),
+        ];
+
+        for transform in [
+            Transform::rotate(Angle::deg(30.0)),
+            Transform::rotate(Angle::deg(-120.0)),
+            Transform::skew(Angle::deg(20.0), Angle::deg(-10.0)),
+            Transform::scale(Ratio::new(-1.5), Ratio::new(0.5)),
+        ] {
+            let shape = Geometry::Rect(size).filled(Color::BLACK.into());
+            let mut frame = Frame::soft(size);
+            frame.push(Point::zero(), FrameItem::Shape(shape, Span::detached()));
+            frame.transform_bounded(transform);
+
+            let (pos, FrameItem::Group(group)) = frame.items().next().unwrap() else {
+                panic!("expected a group");
+            };
+            let ts = Transform::translate(pos.x, pos.y).pre_concat(group.transform);
+            let points = corners.map(|point| point.transform(ts));
+            let min = points.iter().fold(points[0], |min, &p| min.min(p));
+            let max = points.iter().fold(points[0], |max, &p| max.max(p));
+
+            // The frame tightly encloses the transformed shape.
+            assert!(min.x.approx_eq(Abs::zero()) && min.y.approx_eq(Abs::zero()));
+            assert!(max.x.approx_eq(frame.width()));
+            assert!(max.y.approx_eq(frame.height()));
+        }
+    }
+
+    #[test]
+    fn test_push_shape_requires_uniform_stroke_for_non_rect() {
+        let size = Size::splat(Abs::pt(10.0));
+        let path = Geometry::Path(Path::rect(size));
+        let stroke = Some(FixedStroke::default());
+        let mut frame = Frame::soft(size);
+
+        let uniform = Sides::splat(stroke.clone());
+        assert!(frame
+            .push_shape(Point::zero(), path.clone(), None, uniform, Span::detached())
+            .is_ok());
+        assert_eq!(frame.items().count(), 1);
+
+        let partial = Sides { top: None, ..Sides::splat(stroke) };
+        assert!(frame
+            .push_shape(Point::zero(), path, None, partial.clone(), Span::detached())
+            .is_err());
+        assert_eq!(frame.items().count(), 1);
+
+        let rect = Geometry::Rect(size);
+        assert!(frame
+            .push_shape(Point::zero(), rect, None, partial, Span::detached())
+            .is_ok());
+    }
+}
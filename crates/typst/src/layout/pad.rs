@@ -1,7 +1,9 @@
 use crate::diag::SourceResult;
 use crate::engine::Engine;
 use crate::foundations::{elem, Content, Resolve, StyleChain};
-use crate::layout::{Abs, Fragment, Layout, Length, Point, Regions, Rel, Sides, Size};
+use crate::layout::{
+    Abs, Axes, FixedAlign, Fragment, Layout, Length, Point, Regions, Rel, Sides, Size,
+};
 
 /// Adds spacing around content.
 ///
@@ -89,6 +91,14 @@ impl Layout for PadElem {
             // Grow the frame and translate everything in the frame inwards.
             frame.set_size(padded);
             frame.translate(offset);
+
+            // A negative padding can exceed the size of the content. Then, the
+            // frame collapses around the center of the content instead of
+            // taking on a negative size.
+            if padded.x < Abs::zero() || padded.y < Abs::zero() {
+                let collapsed = padded.max(Size::zero());
+                frame.resize(collapsed, Axes::splat(FixedAlign::Center));
+            }
         }
 
         Ok(fragment)
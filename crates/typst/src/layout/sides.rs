@@ -5,7 +5,8 @@ use crate::diag::{bail, StrResult};
 use crate::foundations::{
     cast, CastInfo, Dict, Fold, FromValue, IntoValue, Reflect, Resolve, StyleChain, Value,
 };
-use crate::layout::{Abs, Align, Axes, Axis, Corner, Rel, Size};
+use crate::layout::{Abs, Align, Axes, Axis, Corner, Dir, Rel, Size};
+use crate::text::TextElem;
 use crate::util::Get;
 
 /// A container with left, top, right and bottom components.
@@ -236,6 +237,131 @@ impl<T: Fold> Fold for Sides<Option<T>> {
     }
 }
 
+/// Sides that can also be given relative to the text direction.
+///
+/// The `start` and `end` components map to the left and right side depending
+/// on the direction of the text when the sides are resolved. They take
+/// precedence over the physical left and right side.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DirSides<T> {
+    /// The physical sides.
+    pub sides: Sides<T>,
+    /// The value for the side where text starts.
+    pub start: T,
+    /// The value for the side where text ends.
+    pub end: T,
+}
+
+impl<T: Default> From<Sides<T>> for DirSides<T> {
+    fn from(sides: Sides<T>) -> Self {
+        Self { sides, start: T::default(), end: T::default() }
+    }
+}
+
+impl<T: Debug + PartialEq> Debug for DirSides<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("DirSides")
+            .field("sides", &self.sides)
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .finish()
+    }
+}
+
+impl<T: Reflect> Reflect for DirSides<Option<T>> {
+    fn input() -> CastInfo {
+        T::input() + Dict::input()
+    }
+
+    fn output() -> CastInfo {
+        T::output() + Dict::output()
+    }
+
+    fn castable(value: &Value) -> bool {
+        Dict::castable(value) || T::castable(value)
+    }
+}
+
+impl<T> IntoValue for DirSides<T>
+where
+    T: PartialEq + IntoValue,
+{
+    fn into_value(self) -> Value {
+        let start = self.start.into_value();
+        let end = self.end.into_value();
+        if start == Value::None && end == Value::None {
+            return self.sides.into_value();
+        }
+
+        let mut dict = Dict::new();
+        let mut handle = |key: &str, value: Value| {
+            if value != Value::None {
+                dict.insert(key.into(), value);
+            }
+        };
+
+        handle("left", self.sides.left.into_value());
+        handle("top", self.sides.top.into_value());
+        handle("right", self.sides.right.into_value());
+        handle("bottom", self.sides.bottom.into_value());
+        handle("start", start);
+        handle("end", end);
+
+        Value::Dict(dict)
+    }
+}
+
+impl<T> FromValue for DirSides<Option<T>>
+where
+    T: Default + FromValue + Clone,
+{
+    fn from_value(mut value: Value) -> StrResult<Self> {
+        let keys = ["left", "top", "right", "bottom", "x", "y", "rest", "start", "end"];
+        if let Value::Dict(dict) = &mut value {
+            if dict.iter().any(|(key, _)| keys.contains(&key.as_str())) {
+                let mut take = |key| dict.take(key).ok().map(T::from_value).transpose();
+                let rest = take("rest")?;
+                let x = take("x")?.or_else(|| rest.clone());
+                let y = take("y")?.or_else(|| rest.clone());
+                let sides = Sides {
+                    left: take("left")?.or_else(|| x.clone()),
+                    top: take("top")?.or_else(|| y.clone()),
+                    right: take("right")?.or_else(|| x.clone()),
+                    bottom: take("bottom")?.or_else(|| y.clone()),
+                };
+                let start = take("start")?;
+                let end = take("end")?;
+
+                dict.finish(&keys)?;
+                return Ok(Self { sides, start, end });
+            }
+        }
+
+        if T::castable(&value) {
+            Ok(Sides::splat(Some(T::from_value(value)?)).into())
+        } else {
+            Err(Self::error(&value))
+        }
+    }
+}
+
+impl<T: Resolve> Resolve for DirSides<Option<T>> {
+    type Output = Sides<Option<T::Output>>;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        let (left, right) = match TextElem::dir_in(styles) {
+            Dir::RTL => (self.end, self.start),
+            _ => (self.start, self.end),
+        };
+        let sides = Sides {
+            left: left.or(self.sides.left),
+            right: right.or(self.sides.right),
+            ..self.sides
+        };
+        sides.resolve(styles)
+    }
+}
+
 /// The four sides of objects.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Side {
@@ -237,6 +237,16 @@ impl Transform {
         }
     }
 
+    /// A skew transform, which slants vertical lines by `ax` and horizontal
+    /// lines by `ay`.
+    pub fn skew(ax: Angle, ay: Angle) -> Self {
+        Self {
+            kx: Ratio::new(ax.tan()),
+            ky: Ratio::new(ay.tan()),
+            ..Self::identity()
+        }
+    }
+
     /// Whether this is the identity transformation.
     pub fn is_identity(self) -> bool {
         self == Self::identity()
@@ -117,7 +117,9 @@ impl LayoutMath for CancelElem {
         let span = self.span();
         let length = self.length(styles).resolve(styles);
 
-        let stroke = self.stroke(styles).unwrap_or(FixedStroke {
+        let stroke = self.stroke(styles);
+        stroke.ensure_absolute(span)?;
+        let stroke = stroke.unwrap_or(FixedStroke {
             paint: TextElem::fill_in(styles).as_decoration(),
             ..Default::default()
         });
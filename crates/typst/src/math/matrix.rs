@@ -15,7 +15,7 @@ use crate::math::{
 use crate::syntax::{Span, Spanned};
 use crate::text::TextElem;
 use crate::util::Numeric;
-use crate::visualize::{FixedStroke, Geometry, LineCap, Shape, Stroke};
+use crate::visualize::{FillRule, FixedStroke, Geometry, LineCap, Shape, Stroke};
 
 const DEFAULT_ROW_GAP: Em = Em::new(0.5);
 const DEFAULT_COL_GAP: Em = Em::new(0.5);
@@ -219,6 +219,10 @@ impl LayoutMath for MatElem {
         let rows = self.rows();
 
         if let Some(aug) = &augment {
+            if let Smart::Custom(stroke) = &aug.stroke {
+                stroke.ensure_absolute(self.span())?;
+            }
+
             for &offset in &aug.hline.0 {
                 if offset == 0 || offset.unsigned_abs() >= rows.len() {
                     bail!(
@@ -536,6 +540,7 @@ fn line_item(length: Abs, vertical: bool, stroke: FixedStroke, span: Span) -> Fr
             geometry: line_geom,
             fill: None,
             stroke: Some(stroke),
+            fill_rule: FillRule::default(),
         },
         span,
     )
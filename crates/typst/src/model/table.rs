@@ -191,7 +191,11 @@ impl Layout for TableElem {
             .collect::<SourceResult<_>>()?;
 
         let fill = self.fill(styles);
-        let stroke = self.stroke(styles).map(Stroke::unwrap_or_default);
+        let stroke = self.stroke(styles);
+        stroke
+            .iter()
+            .try_for_each(|stroke| stroke.ensure_absolute(self.span()))?;
+        let stroke = stroke.map(Stroke::unwrap_or_default);
 
         // Prepare grid layout by unifying content and gutter tracks.
         let layouter =
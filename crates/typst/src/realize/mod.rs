@@ -66,7 +66,7 @@ pub fn realize_block<'a>(
     if content.can::<dyn Layout>()
         && !content.is::<BoxElem>()
         && !content.is::<LineElem>()
-        && !content.is::<RectElem>()
+        && !content.to::<RectElem>().is_some_and(|rect| !rect.breakable(styles))
         && !content.is::<SquareElem>()
         && !content.is::<EllipseElem>()
         && !content.is::<CircleElem>()
@@ -5,7 +5,9 @@ use ecow::{eco_format, EcoString};
 
 use crate::diag::SourceResult;
 use crate::engine::Engine;
-use crate::foundations::{cast, elem, ty, Content, Fold, Repr, Show, Smart, StyleChain};
+use crate::foundations::{
+    cast, elem, ty, Content, Fold, NativeElement, Repr, Show, Smart, StyleChain,
+};
 use crate::layout::{Abs, Em, Frame, FrameItem, Length, Point, Size};
 use crate::syntax::Span;
 use crate::text::{
@@ -87,9 +89,11 @@ pub struct UnderlineElem {
 impl Show for UnderlineElem {
     #[tracing::instrument(name = "UnderlineElem::show", skip_all)]
     fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let stroke = self.stroke(styles).unwrap_or_default();
+        stroke.ensure_absolute(self.span())?;
         Ok(self.body().clone().styled(TextElem::set_deco(Decoration {
             line: DecoLine::Underline {
-                stroke: self.stroke(styles).unwrap_or_default(),
+                stroke,
                 offset: self.offset(styles),
                 evade: self.evade(styles),
                 background: self.background(styles),
@@ -179,9 +183,11 @@ pub struct OverlineElem {
 impl Show for OverlineElem {
     #[tracing::instrument(name = "OverlineElem::show", skip_all)]
     fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let stroke = self.stroke(styles).unwrap_or_default();
+        stroke.ensure_absolute(self.span())?;
         Ok(self.body().clone().styled(TextElem::set_deco(Decoration {
             line: DecoLine::Overline {
-                stroke: self.stroke(styles).unwrap_or_default(),
+                stroke,
                 offset: self.offset(styles),
                 evade: self.evade(styles),
                 background: self.background(styles),
@@ -256,10 +262,12 @@ pub struct StrikeElem {
 impl Show for StrikeElem {
     #[tracing::instrument(name = "StrikeElem::show", skip_all)]
     fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+        let stroke = self.stroke(styles).unwrap_or_default();
+        stroke.ensure_absolute(self.span())?;
         Ok(self.body().clone().styled(TextElem::set_deco(Decoration {
             // Note that we do not support evade option for strikethrough.
             line: DecoLine::Strikethrough {
-                stroke: self.stroke(styles).unwrap_or_default(),
+                stroke,
                 offset: self.offset(styles),
                 background: self.background(styles),
             },
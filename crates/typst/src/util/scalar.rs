@@ -95,7 +95,9 @@ impl PartialOrd for Scalar {
 impl Hash for Scalar {
     fn hash<H: Hasher>(&self, state: &mut H) {
         debug_assert!(!self.0.is_nan(), "float is NaN");
-        self.0.to_bits().hash(state);
+        // Adding zero turns a negative zero into a positive one, since the two
+        // compare equal and thus must hash equally.
+        (self.0 + 0.0).to_bits().hash(state);
     }
 }
 
@@ -0,0 +1,169 @@
+use crate::diag::{bail, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{elem, Cast, NativeElement, Smart, StyleChain};
+use crate::layout::{
+    Abs, Angle, Fragment, Frame, FrameItem, GroupItem, Layout, Length, Point, Regions,
+    Size,
+};
+use crate::util::Numeric;
+use crate::visualize::{
+    ellipse_arc_point, FillRule, FixedStroke, Geometry, Paint, Path, PathExt, RelativeTo,
+    Shape, Stroke,
+};
+
+/// A circular arc, optionally closed into a chord or a pie slice.
+///
+/// The arc's frame spans the full circle, so that arcs with the same radius
+/// can be placed on top of each other to form a chart.
+///
+/// # Example
+/// ```example
+/// #arc(start: 0deg, end: 270deg)
+/// #arc(
+///   end: 120deg,
+///   mode: "pie",
+///   fill: blue.lighten(60%),
+///   stroke: blue,
+/// )
+/// ```
+#[elem(Layout)]
+pub struct ArcElem {
+    /// The angle at which the arc starts. Like a [line's]($line.angle) angle,
+    /// it is measured clockwise from the positive x-axis.
+    pub start: Angle,
+
+    /// The angle at which the arc ends. If this is smaller than `start`, the
+    /// arc runs counterclockwise.
+    #[default(Angle::deg(90.0))]
+    pub end: Angle,
+
+    /// The radius of the arc's circle.
+    #[resolve]
+    #[default(Abs::pt(15.0).into())]
+    pub radius: Length,
+
+    /// How to close the arc.
+    ///
+    /// ```example
+    /// #set arc(end: 240deg, stroke: 2pt)
+    /// #stack(
+    ///   dir: ltr,
+    ///   spacing: 1em,
+    ///   arc(mode: "open"),
+    ///   arc(mode: "chord"),
+    ///   arc(mode: "pie"),
+    /// )
+    /// ```
+    pub mode: ArcMode,
+
+    /// How to fill the arc. An open arc is filled as if it were a chord.
+    ///
+    /// When setting a fill, the default stroke disappears. To create an arc
+    /// with both fill and stroke, you have to configure both.
+    ///
+    /// A [gradient]($gradient) spans the arc's whole circle, so that a
+    /// [conic gradient]($gradient.conic) sweeps around the circle's center.
+    ///
+    /// ```example
+    /// #let grad = gradient.conic(..color.map.rainbow)
+    /// #arc(end: 120deg, mode: "pie", fill: grad)
+    /// ```
+    pub fill: Option<Paint>,
+
+    /// How to [stroke]($stroke) the arc.
+    ///
+    /// Can be set to  `{none}` to disable the stroke or to `{auto}` for a
+    /// stroke of `{1pt}` black if and if only if no fill is given.
+    #[resolve]
+    #[fold]
+    pub stroke: Smart<Option<Stroke>>,
+}
+
+impl Layout for ArcElem {
+    #[tracing::instrument(name = "ArcElem::layout", skip_all)]
+    fn layout(
+        &self,
+        _: &mut Engine,
+        styles: StyleChain,
+        _: Regions,
+    ) -> SourceResult<Fragment> {
+        let radius = self.radius(styles);
+        if !radius.is_finite() || radius < Abs::zero() {
+            bail!(self.span(), "arc radius must be finite and non-negative");
+        }
+
+        // Prepare fill and stroke.
+        let fill = self.fill(styles);
+        let stroke = self.stroke(styles);
+        if let Smart::Custom(Some(stroke)) = &stroke {
+            stroke.ensure_absolute(self.span())?;
+        }
+        let stroke = match stroke {
+            Smart::Auto if fill.is_none() => Some(FixedStroke::default()),
+            Smart::Auto => None,
+            Smart::Custom(stroke) => stroke.map(Stroke::unwrap_or_default),
+        };
+
+        let start = self.start(styles).to_rad();
+        let end = self.end(styles).to_rad();
+        let center = Point::splat(radius);
+        let radius = Size::splat(radius);
+
+        let mut path = Path::new();
+        path.move_to(ellipse_arc_point(center, radius, start));
+        path.ellipse_arc(center, radius, start, end);
+        match self.mode(styles) {
+            ArcMode::Open => {}
+            ArcMode::Chord => path.close_path(),
+            ArcMode::Pie => {
+                path.line_to(center);
+                path.close_path();
+            }
+        }
+
+        let size = radius * 2.0;
+        let mut frame = Frame::hard(size);
+        let mut shape = Shape {
+            geometry: Geometry::Path(path),
+            stroke,
+            fill,
+            fill_rule: FillRule::default(),
+        };
+
+        // Gradients and patterns should span the whole circle rather than
+        // just the arc's bounding box, so that, for example, a conic gradient
+        // is centered on the circle. To this end, the arc is drawn into a
+        // group with the circle's size and its paints are made relative to
+        // that group, unless they are explicitly relative to the parent.
+        let paints = || shape.fill.iter().chain(shape.stroke.as_ref().map(|s| &s.paint));
+        let is_parent =
+            |paint: &Paint| paint.relative() == Smart::Custom(RelativeTo::Parent);
+        let is_self =
+            |paint: &Paint| !matches!(paint, Paint::Solid(_)) && !is_parent(paint);
+        if paints().any(is_self) && !paints().any(is_parent) {
+            shape.fill = shape.fill.as_ref().map(Paint::as_decoration);
+            if let Some(stroke) = &mut shape.stroke {
+                stroke.paint = stroke.paint.as_decoration();
+            }
+            let mut group = Frame::hard(size);
+            group.push(Point::zero(), FrameItem::Shape(shape, self.span()));
+            frame.push(Point::zero(), FrameItem::Group(GroupItem::new(group)));
+        } else {
+            frame.push(Point::zero(), FrameItem::Shape(shape, self.span()));
+        }
+
+        Ok(Fragment::frame(frame))
+    }
+}
+
+/// How to close an arc.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum ArcMode {
+    /// Leave the arc open.
+    #[default]
+    Open,
+    /// Close the arc with a straight line between its end points.
+    Chord,
+    /// Close the arc through the circle's center, forming a pie slice.
+    Pie,
+}
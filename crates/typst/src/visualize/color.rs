@@ -1144,6 +1144,22 @@ impl Color {
         }
     }
 
+    /// Scales the alpha channel of the color by the given factor.
+    ///
+    /// Colors without an alpha channel are converted to RGB first.
+    pub fn scale_alpha(self, factor: f32) -> Self {
+        if factor == 1.0 {
+            return self;
+        }
+
+        let color = match self.alpha() {
+            Some(_) => self,
+            None => self.to_rgb(),
+        };
+        let alpha = color.alpha().unwrap_or(1.0);
+        color.with_alpha(alpha * factor)
+    }
+
     /// Sets the alpha channel of the color, if it has one.
     pub fn with_alpha(mut self, alpha: f32) -> Self {
         match &mut self {
@@ -1520,11 +1536,20 @@ impl Eq for Color {}
 impl Hash for Color {
     fn hash<H: Hasher>(&self, state: &mut H) {
         core::mem::discriminant(self).hash(state);
-        let [x, y, z, w] = self.to_vec4();
-        x.to_bits().hash(state);
-        y.to_bits().hash(state);
-        z.to_bits().hash(state);
-        w.to_bits().hash(state);
+
+        // Hash with the same precision that `eq` compares with, so that equal
+        // colors have equal hashes.
+        match self {
+            Self::Rgb(_) => self.to_vec4_u8().hash(state),
+            Self::Luma(c) => ((c.luma * 255.0).round() as u8).hash(state),
+            _ => {
+                // Adding zero turns a negative zero, which compares equal to
+                // zero, into a positive one.
+                for component in self.to_vec4() {
+                    (component + 0.0).to_bits().hash(state);
+                }
+            }
+        }
     }
 }
 
@@ -1873,6 +1898,31 @@ preset!(crest; 0xa5cd90ff, 0xa4cc90ff, 0xa3cc91ff, 0xa2cb91ff, 0xa0cb91ff, 0x9fc
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_equal_colors_hash_equally() {
+        #[track_caller]
+        fn test(a: Color, b: Color) {
+            use std::collections::hash_map::DefaultHasher;
+            let hash = |color: Color| {
+                let mut state = DefaultHasher::new();
+                color.hash(&mut state);
+                state.finish()
+            };
+            assert_eq!(a, b);
+            assert_eq!(hash(a), hash(b));
+        }
+
+        test(
+            Color::from_u8(10, 20, 30, 255),
+            Color::Rgb(Rgb::new(0.0393, 0.0785, 0.1177, 1.0)),
+        );
+        test(Color::Luma(Luma::new(0.5)), Color::Luma(Luma::new(0.501)));
+        test(
+            Color::Oklab(Oklab::new(0.5, -0.0, 0.0, 1.0)),
+            Color::Oklab(Oklab::new(0.5, 0.0, 0.0, 1.0)),
+        );
+    }
+
     #[test]
     fn test_parse_color_strings() {
         #[track_caller]
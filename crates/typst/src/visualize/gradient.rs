@@ -278,7 +278,7 @@ impl Gradient {
     /// )
     /// ```
     #[func]
-    fn radial(
+    pub fn radial(
         /// The call site of this function.
         span: Span,
         /// The color [stops](#stops) of the gradient.
@@ -341,6 +341,10 @@ impl Gradient {
             );
         }
 
+        if radius.v <= Ratio::zero() {
+            bail!(radius.span, "the end radius must be greater than 0%");
+        }
+
         if focal_radius.v > radius.v {
             bail!(
                 focal_radius.span,
@@ -743,6 +747,21 @@ impl Gradient {
 
         self
     }
+
+    /// Clones this gradient, but with the alpha of all stops scaled by the
+    /// given factor.
+    pub fn scale_alpha(mut self, factor: f32) -> Self {
+        let stops = match &mut self {
+            Self::Linear(linear) => &mut Arc::make_mut(linear).stops,
+            Self::Radial(radial) => &mut Arc::make_mut(radial).stops,
+            Self::Conic(conic) => &mut Arc::make_mut(conic).stops,
+        };
+        for (color, _) in stops {
+            *color = color.scale_alpha(factor);
+        }
+        self
+    }
+
     /// Returns a reference to the stops of this gradient.
     pub fn stops_ref(&self) -> &[(Color, Ratio)] {
         match self {
@@ -1181,7 +1200,7 @@ fn process_stops(stops: &[Spanned<GradientStop>]) -> SourceResult<Vec<(Color, Ra
             .iter()
             .map(|Spanned { v: GradientStop { color, offset }, span }| {
                 if offset.unwrap().get() > 1.0 || offset.unwrap().get() < 0.0 {
-                    bail!(*span, "offset must be between 0 and 1");
+                    bail!(*span, "offset must be between 0% and 100%");
                 }
                 Ok((*color, offset.unwrap()))
             })
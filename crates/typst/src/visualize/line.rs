@@ -1,11 +1,15 @@
 use crate::diag::{bail, SourceResult};
 use crate::engine::Engine;
-use crate::foundations::{elem, NativeElement, StyleChain};
+use crate::foundations::{elem, Cast, NativeElement, Smart, StyleChain};
 use crate::layout::{
-    Abs, Angle, Axes, Fragment, Frame, FrameItem, Layout, Length, Regions, Rel, Size,
+    Abs, Angle, Axes, Fragment, Frame, FrameItem, Layout, Length, Point, Regions, Rel,
+    Size,
 };
 use crate::util::Numeric;
-use crate::visualize::{Geometry, Stroke};
+use crate::visualize::{ellipse, FixedStroke, Geometry, Path, Shape, Stroke, Taper};
+
+/// How much larger than the stroke's thickness markers are by default.
+const MARKER_SCALE: f64 = 4.0;
 
 /// A line from one point to another.
 ///
@@ -56,6 +60,49 @@ pub struct LineElem {
     #[resolve]
     #[fold]
     pub stroke: Stroke,
+
+    /// Tapers the line's stroke from a thickness at its start to one at its
+    /// end, given as an array of two lengths. This overrides the stroke's own
+    /// thickness. A tapered line has no dash pattern or line caps.
+    ///
+    /// ```example
+    /// #set line(length: 100%, stroke: maroon)
+    /// #stack(
+    ///   spacing: 1em,
+    ///   line(taper: (4pt, 0pt)),
+    ///   line(taper: (0.5pt, 3pt)),
+    /// )
+    /// ```
+    #[resolve]
+    pub taper: Option<Taper>,
+
+    /// The marker at the line's start point.
+    ///
+    /// ```example
+    /// #set line(length: 3cm, stroke: 1.5pt)
+    /// #stack(
+    ///   spacing: 1em,
+    ///   line(marker-start: "arrow", marker-end: "arrow"),
+    ///   line(marker-start: "bar", marker-end: "bar"),
+    ///   line(marker-start: "circle", marker-end: "arrow"),
+    /// )
+    /// ```
+    pub marker_start: Option<LineMarker>,
+
+    /// The marker at the line's end point.
+    ///
+    /// ```example
+    /// #line(end: (4cm, 1cm), marker-end: "arrow")
+    /// ```
+    pub marker_end: Option<LineMarker>,
+
+    /// The size of the line's markers. For arrows, this is both their length
+    /// and their width.
+    ///
+    /// If set to `{auto}`, markers are four times as large as the stroke is
+    /// thick.
+    #[resolve]
+    pub marker_size: Smart<Length>,
 }
 
 impl Layout for LineElem {
@@ -78,7 +125,10 @@ impl Layout for LineElem {
                 resolve(Axes::new(x, y))
             });
 
-        let stroke = self.stroke(styles).unwrap_or_default();
+        let stroke = self.stroke(styles);
+        stroke.ensure_absolute(self.span())?;
+        let stroke = stroke.unwrap_or_default();
+        let taper = self.taper(styles);
         let size = start.max(start + delta).max(Size::zero());
         let target = regions.expand.select(regions.size, size);
 
@@ -86,9 +136,101 @@ impl Layout for LineElem {
             bail!(self.span(), "cannot create line with infinite length");
         }
 
+        let mut from = start.to_point();
+        let mut to = (start + delta).to_point();
+        let mut markers = vec![];
+
+        // Markers need a direction, so there are none for a line without
+        // length.
+        let length = delta.to_point().hypot();
+        if length > Abs::zero() {
+            // With a taper, each marker is sized for the thickness at its
+            // end of the line.
+            let marker_size = self.marker_size(styles);
+            let stroke_at = |thickness: Option<Abs>| {
+                let thickness = thickness.unwrap_or(stroke.thickness);
+                let size = marker_size.unwrap_or(thickness * MARKER_SCALE);
+                (FixedStroke { thickness, ..stroke.clone() }, size)
+            };
+            if let Some(marker) = self.marker_start(styles) {
+                let (stroke, size) = stroke_at(taper.map(|t| t.start));
+                let outward = delta.to_point() * (size / length);
+                let (shapes, retreat) = layout_marker(marker, from, -outward, &stroke);
+                from += outward * retreat.min(length / (2.0 * size));
+                markers.extend(shapes);
+            }
+            if let Some(marker) = self.marker_end(styles) {
+                let (stroke, size) = stroke_at(taper.map(|t| t.end));
+                let outward = delta.to_point() * (size / length);
+                let (shapes, retreat) = layout_marker(marker, to, outward, &stroke);
+                to -= outward * retreat.min(length / (2.0 * size));
+                markers.extend(shapes);
+            }
+        }
+
         let mut frame = Frame::soft(target);
-        let shape = Geometry::Line(delta.to_point()).stroked(stroke);
-        frame.push(start.to_point(), FrameItem::Shape(shape, self.span()));
+        let shape = match taper {
+            Some(taper) => {
+                let mut path = Path::new();
+                path.move_to(Point::zero());
+                path.line_to(to - from);
+                Geometry::Path(path.tapered(taper.start, taper.end))
+                    .filled(stroke.paint.clone())
+            }
+            None => Geometry::Line(to - from).stroked(stroke),
+        };
+        frame.push(from, FrameItem::Shape(shape, self.span()));
+        for (pos, shape) in markers {
+            frame.push(pos, FrameItem::Shape(shape, self.span()));
+        }
+
         Ok(Fragment::frame(frame))
     }
 }
+
+/// A decoration at the start or end of a line.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum LineMarker {
+    /// A filled triangle pointing away from the line.
+    Arrow,
+    /// A bar across the line.
+    Bar,
+    /// A filled circle centered on the line's end point.
+    Circle,
+}
+
+/// Produce the shapes for a marker at `point`, where `outward` points away
+/// from the line and is as long as the marker is large.
+///
+/// Also returns by how much the line should be shortened, as a fraction of
+/// the marker's size, so that it does not poke out of the marker.
+fn layout_marker(
+    marker: LineMarker,
+    point: Point,
+    outward: Point,
+    stroke: &FixedStroke,
+) -> (Vec<(Point, Shape)>, f64) {
+    let across = Point::new(-outward.y, outward.x) / 2.0;
+    match marker {
+        LineMarker::Arrow => {
+            let base = point - outward;
+            let mut path = Path::new();
+            path.move_to(point);
+            path.line_to(base + across);
+            path.line_to(base - across);
+            path.close_path();
+            let shape = Geometry::Path(path).filled(stroke.paint.clone());
+            (vec![(Point::zero(), shape)], 0.5)
+        }
+        LineMarker::Bar => {
+            let stroke = FixedStroke { dash_pattern: None, ..stroke.clone() };
+            let shape = Geometry::Line(across * 2.0).stroked(stroke);
+            (vec![(point - across, shape)], 0.0)
+        }
+        LineMarker::Circle => {
+            let size = outward.hypot();
+            let shape = ellipse(Size::splat(size), Some(stroke.paint.clone()), None);
+            (vec![(point - Point::splat(size / 2.0), shape)], 0.0)
+        }
+    }
+}
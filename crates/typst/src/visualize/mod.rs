@@ -1,5 +1,6 @@
 //! Drawing and visualization.
 
+mod arc;
 mod color;
 mod gradient;
 mod image;
@@ -8,9 +9,13 @@ mod paint;
 mod path;
 mod pattern;
 mod polygon;
+mod shadow;
 mod shape;
+mod shapes;
 mod stroke;
+mod theme;
 
+pub use self::arc::*;
 pub use self::color::*;
 pub use self::gradient::*;
 pub use self::image::*;
@@ -19,8 +24,11 @@ pub use self::paint::*;
 pub use self::path::*;
 pub use self::pattern::*;
 pub use self::polygon::*;
+pub use self::shadow::*;
 pub use self::shape::*;
+pub use self::shapes::*;
 pub use self::stroke::*;
+pub use self::theme::*;
 
 use crate::foundations::{category, Category, Scope};
 
@@ -46,5 +54,8 @@ pub(super) fn define(global: &mut Scope) {
     global.define_elem::<EllipseElem>();
     global.define_elem::<CircleElem>();
     global.define_elem::<PolygonElem>();
+    global.define_elem::<ArcElem>();
     global.define_elem::<PathElem>();
+    global.define_elem::<ShapesElem>();
+    global.define_elem::<ThemeElem>();
 }
@@ -3,6 +3,7 @@ use std::fmt::{self, Debug, Formatter};
 use ecow::EcoString;
 
 use crate::foundations::{cast, Repr, Smart};
+use crate::layout::Ratio;
 use crate::visualize::{Color, Gradient, Pattern, RelativeTo};
 
 /// How a fill or stroke should be painted.
@@ -34,6 +35,23 @@ impl Paint {
         }
     }
 
+    /// Makes this paint more transparent by scaling its alpha with the given
+    /// opacity.
+    ///
+    /// Patterns are left unchanged.
+    pub fn with_opacity(self, opacity: Ratio) -> Self {
+        let factor = opacity.get().clamp(0.0, 1.0) as f32;
+        if factor == 1.0 {
+            return self;
+        }
+
+        match self {
+            Self::Solid(color) => Self::Solid(color.scale_alpha(factor)),
+            Self::Gradient(gradient) => Self::Gradient(gradient.scale_alpha(factor)),
+            Self::Pattern(pattern) => Self::Pattern(pattern),
+        }
+    }
+
     /// Turns this paint into a paint for a text decoration.
     ///
     /// If this paint is a gradient, it will be converted to a gradient with
@@ -1,14 +1,18 @@
-use kurbo::{CubicBez, ParamCurveExtrema};
+use ecow::{eco_format, EcoString};
+use kurbo::{CubicBez, ParamCurve, ParamCurveExtrema};
 
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    array, cast, elem, Array, NativeElement, Reflect, Resolve, Smart, StyleChain,
+    array, cast, elem, func, scope, Array, Content, NativeElement, Reflect, Resolve,
+    Smart, StyleChain,
 };
 use crate::layout::{
     Abs, Axes, Fragment, Frame, FrameItem, Layout, Length, Point, Regions, Rel, Size,
 };
-use crate::visualize::{FixedStroke, Geometry, Paint, Shape, Stroke};
+use crate::syntax::Spanned;
+use crate::util::Numeric;
+use crate::visualize::{FillRule, FixedStroke, Geometry, Paint, Shape, Stroke, Taper};
 
 use PathVertex::{AllControlPoints, MirroredControlPoint, Vertex};
 
@@ -25,17 +29,22 @@ use PathVertex::{AllControlPoints, MirroredControlPoint, Vertex};
 ///   ((50%, 0pt), (40pt, 0pt)),
 /// )
 /// ```
-#[elem(Layout)]
+#[elem(scope, Layout)]
 pub struct PathElem {
     /// How to fill the path.
     ///
     /// When setting a fill, the default stroke disappears. To create a
     /// rectangle with both fill and stroke, you have to configure both.
     ///
-    /// Currently all paths are filled according to the [non-zero winding
-    /// rule](https://en.wikipedia.org/wiki/Nonzero-rule).
+    /// Which regions of a self-intersecting path are filled is decided by the
+    /// [`fill-rule`]($path.fill-rule).
     pub fill: Option<Paint>,
 
+    /// How to fill the regions where the path intersects itself. See the
+    /// [polygon's documentation]($polygon.fill-rule) for more details.
+    #[default(FillRule::NonZero)]
+    pub fill_rule: FillRule,
+
     /// How to [stroke]($stroke) the path. This can be:
     ///
     /// Can be set to  `{none}` to disable the stroke or to `{auto}` for a
@@ -44,6 +53,24 @@ pub struct PathElem {
     #[fold]
     pub stroke: Smart<Option<Stroke>>,
 
+    /// Tapers the path's stroke from a thickness at its start to one at its
+    /// end. See the [line's documentation]($line.taper) for more details.
+    ///
+    /// The thickness changes in proportion to the distance along the path.
+    ///
+    /// ```example
+    /// #path(
+    ///   stroke: eastern,
+    ///   taper: (0.5pt, 5pt),
+    ///   (0pt, 30pt),
+    ///   ((40pt, 0pt), (-20pt, 0pt)),
+    ///   ((80pt, 30pt), (-20pt, 0pt)),
+    ///   (120pt, 0pt),
+    /// )
+    /// ```
+    #[resolve]
+    pub taper: Option<Taper>,
+
     /// Whether to close this path with one last bezier curve. This curve will
     /// takes into account the adjacent control points. If you want to close
     /// with a straight line, simply add one last point that's the same as the
@@ -68,6 +95,56 @@ pub struct PathElem {
     ///   respectively).
     #[variadic]
     pub vertices: Vec<PathVertex>,
+
+    /// SVG path data that defines the path instead of the vertices.
+    #[internal]
+    pub data: Option<EcoString>,
+}
+
+#[scope]
+impl PathElem {
+    /// A path defined by [SVG path data](https://developer.mozilla.org/en-US/docs/Web/SVG/Attribute/d).
+    ///
+    /// The `M`, `L`, `H`, `V`, `C`, `S`, `Q`, `T`, and `Z` commands are
+    /// supported, both in their absolute (uppercase) and relative (lowercase)
+    /// form. Coordinates are given in points. Like other paths, the path is
+    /// sized such that it spans from the origin to its rightmost and lowest
+    /// point.
+    ///
+    /// ```example
+    /// #path.svg(
+    ///   fill: blue.lighten(80%),
+    ///   stroke: blue,
+    ///   "M 0 30 Q 30 0 60 30 T 120 30 V 45 H 0 Z",
+    /// )
+    /// ```
+    #[func(title = "SVG Path")]
+    pub fn svg(
+        /// How to fill the path. See the general
+        /// [path's documentation]($path.fill) for more details.
+        #[named]
+        fill: Option<Option<Paint>>,
+
+        /// How to stroke the path. See the general
+        /// [path's documentation]($path.stroke) for more details.
+        #[named]
+        stroke: Option<Smart<Option<Stroke>>>,
+
+        /// The path data.
+        data: Spanned<EcoString>,
+    ) -> SourceResult<Content> {
+        Path::from_svg(&data.v).at(data.span)?;
+
+        let mut elem = PathElem::new(vec![]);
+        elem.push_data(Some(data.v));
+        if let Some(fill) = fill {
+            elem.push_fill(fill);
+        }
+        if let Some(stroke) = stroke {
+            elem.push_stroke(stroke);
+        }
+        Ok(elem.pack())
+    }
 }
 
 impl Layout for PathElem {
@@ -84,6 +161,15 @@ impl Layout for PathElem {
                 .to_point()
         };
 
+        if let Smart::Custom(Some(stroke)) = &self.stroke(styles) {
+            stroke.ensure_absolute(self.span())?;
+        }
+
+        if let Some(data) = self.data(styles) {
+            let path = Path::from_svg(&data).at(self.span())?;
+            return Ok(Fragment::frame(self.frame(styles, path.extent(), path)?));
+        }
+
         let vertices = self.vertices();
         let points: Vec<Point> = vertices.iter().map(|c| resolve(c.vertex())).collect();
 
@@ -137,6 +223,17 @@ impl Layout for PathElem {
             path.close_path();
         }
 
+        Ok(Fragment::frame(self.frame(styles, size, path)?))
+    }
+}
+
+impl PathElem {
+    /// Create a frame of the given size with the filled and stroked path.
+    fn frame(&self, styles: StyleChain, size: Size, path: Path) -> SourceResult<Frame> {
+        if !size.is_finite() {
+            bail!(self.span(), "cannot create path with infinite size");
+        }
+
         // Prepare fill and stroke.
         let fill = self.fill(styles);
         let stroke = match self.stroke(styles) {
@@ -146,10 +243,25 @@ impl Layout for PathElem {
         };
 
         let mut frame = Frame::soft(size);
-        let shape = Shape { geometry: Geometry::Path(path), stroke, fill };
-        frame.push(Point::zero(), FrameItem::Shape(shape, self.span()));
 
-        Ok(Fragment::frame(frame))
+        // A tapered stroke is filled separately, on top of the fill.
+        let taper = self.taper(styles).zip(stroke.as_ref());
+        let outline = taper.map(|(taper, stroke)| {
+            Geometry::Path(path.tapered(taper.start, taper.end))
+                .filled(stroke.paint.clone())
+        });
+
+        let shape = Shape {
+            geometry: Geometry::Path(path),
+            stroke: if outline.is_some() { None } else { stroke },
+            fill,
+            fill_rule: self.fill_rule(styles),
+        };
+        frame.push(Point::zero(), FrameItem::Shape(shape, self.span()));
+        if let Some(outline) = outline {
+            frame.push(Point::zero(), FrameItem::Shape(outline, self.span()));
+        }
+        Ok(frame)
     }
 }
 
@@ -247,6 +359,19 @@ impl Path {
         path
     }
 
+    /// Create a closed path through the given vertices.
+    pub fn polygon(points: &[Point]) -> Self {
+        let mut path = Self::new();
+        if let Some((&first, rest)) = points.split_first() {
+            path.move_to(first);
+            for &point in rest {
+                path.line_to(point);
+            }
+            path.close_path();
+        }
+        path
+    }
+
     /// Push a [`MoveTo`](PathItem::MoveTo) item.
     pub fn move_to(&mut self, p: Point) {
         self.0.push(PathItem::MoveTo(p));
@@ -267,6 +392,207 @@ impl Path {
         self.0.push(PathItem::ClosePath);
     }
 
+    /// Move all points of the path by the given offset.
+    pub fn translate(&mut self, offset: Point) {
+        for item in &mut self.0 {
+            match item {
+                PathItem::MoveTo(p) | PathItem::LineTo(p) => *p += offset,
+                PathItem::CubicTo(p1, p2, p3) => {
+                    *p1 += offset;
+                    *p2 += offset;
+                    *p3 += offset;
+                }
+                PathItem::ClosePath => {}
+            }
+        }
+    }
+
+    /// Parse [SVG path data](https://www.w3.org/TR/SVG2/paths.html#PathData).
+    ///
+    /// Supports all commands except for elliptical arcs. Coordinates are
+    /// interpreted as points.
+    pub fn from_svg(data: &str) -> StrResult<Self> {
+        SvgPathParser::new(data).parse()
+    }
+
+    /// The extent of the path from the origin to its rightmost and lowest
+    /// point, including the control points' influence on curves.
+    pub fn extent(&self) -> Size {
+        let mut size = Size::zero();
+        let mut cursor = Point::zero();
+        for item in &self.0 {
+            match *item {
+                PathItem::MoveTo(to) | PathItem::LineTo(to) => cursor = to,
+                PathItem::CubicTo(c0, c1, end) => {
+                    let [p0, p1, p2, p3] = [cursor, c0, c1, end]
+                        .map(|p| kurbo::Point::new(p.x.to_raw(), p.y.to_raw()));
+                    let bbox = CubicBez::new(p0, p1, p2, p3).bounding_box();
+                    size.x.set_max(Abs::raw(bbox.x1));
+                    size.y.set_max(Abs::raw(bbox.y1));
+                    cursor = end;
+                }
+                PathItem::ClosePath => continue,
+            }
+            size = size.max(cursor.to_size());
+        }
+        size
+    }
+
+    /// The outline of a stroke along this path whose thickness changes
+    /// linearly from `start` to `end`, as a closed path to be filled.
+    ///
+    /// Curves are flattened into short line segments whose sides are joined
+    /// with miters. Each subpath gets its own outline, but the thickness
+    /// changes along the length of the whole path.
+    pub fn tapered(&self, start: Abs, end: Abs) -> Self {
+        /// Into how many line segments each curve is flattened.
+        const CURVE_STEPS: usize = 16;
+        /// How far a miter may extend, in multiples of half the thickness.
+        const MITER_LIMIT: f64 = 4.0;
+
+        let raw = |p: Point| kurbo::Point::new(p.x.to_raw(), p.y.to_raw());
+        let push = |lines: &mut Vec<Vec<kurbo::Point>>, from, to| {
+            if lines.is_empty() {
+                lines.push(vec![from]);
+            }
+            let line = lines.last_mut().unwrap();
+            if line.last() != Some(&to) {
+                line.push(to);
+            }
+        };
+
+        // Flatten the path into one polyline per subpath.
+        let mut lines = vec![];
+        let mut cursor = kurbo::Point::ZERO;
+        let mut first = cursor;
+        for item in &self.0 {
+            match *item {
+                PathItem::MoveTo(to) => {
+                    cursor = raw(to);
+                    first = cursor;
+                    lines.push(vec![cursor]);
+                }
+                PathItem::LineTo(to) => {
+                    push(&mut lines, cursor, raw(to));
+                    cursor = raw(to);
+                }
+                PathItem::CubicTo(c0, c1, to) => {
+                    let cubic = CubicBez::new(cursor, raw(c0), raw(c1), raw(to));
+                    for i in 1..=CURVE_STEPS {
+                        let t = i as f64 / CURVE_STEPS as f64;
+                        push(&mut lines, cursor, cubic.eval(t));
+                    }
+                    cursor = raw(to);
+                }
+                PathItem::ClosePath => {
+                    push(&mut lines, cursor, first);
+                    cursor = first;
+                    lines.push(vec![first]);
+                }
+            }
+        }
+
+        let length: f64 = lines
+            .iter()
+            .flat_map(|line| line.windows(2))
+            .map(|w| (w[1] - w[0]).hypot())
+            .sum();
+
+        let mut outline = Self::new();
+        if length <= 0.0 {
+            return outline;
+        }
+
+        let (start, end) = (start.to_raw(), end.to_raw());
+        let mut traveled = 0.0;
+        for line in lines.iter().filter(|line| line.len() >= 2) {
+            let normals: Vec<kurbo::Vec2> = line
+                .windows(2)
+                .map(|w| {
+                    let dir = (w[1] - w[0]).normalize();
+                    kurbo::Vec2::new(-dir.y, dir.x)
+                })
+                .collect();
+
+            let mut left = Vec::with_capacity(line.len());
+            let mut right = Vec::with_capacity(line.len());
+            for (i, &point) in line.iter().enumerate() {
+                if i > 0 {
+                    traveled += (point - line[i - 1]).hypot();
+                }
+
+                // Join adjacent segments with a miter, unless the path turns
+                // back on itself.
+                let before = normals[i.saturating_sub(1)];
+                let after = normals[i.min(normals.len() - 1)];
+                let sum = before + after;
+                let normal = if sum.hypot() < 1e-9 {
+                    after
+                } else {
+                    let miter = sum.normalize();
+                    miter * (1.0 / miter.dot(after)).min(MITER_LIMIT)
+                };
+
+                let half = (start + (end - start) * traveled / length) / 2.0;
+                let to_point = |p: kurbo::Point| Point::new(Abs::raw(p.x), Abs::raw(p.y));
+                left.push(to_point(point + normal * half));
+                right.push(to_point(point - normal * half));
+            }
+
+            outline.move_to(left[0]);
+            for &point in left[1..].iter().chain(right.iter().rev()) {
+                outline.line_to(point);
+            }
+            outline.close_path();
+        }
+
+        outline
+    }
+
+    /// Computes the top-left corner and size of the bounding box of this path.
+    ///
+    /// In contrast to [`bbox_size`](Self::bbox_size), this only covers the
+    /// points the path passes through, which need not include the origin.
+    pub fn bbox(&self) -> (Point, Size) {
+        let mut min = Point::splat(Abs::inf());
+        let mut max = Point::splat(-Abs::inf());
+        let mut include = |point: Point| {
+            min = min.min(point);
+            max = max.max(point);
+        };
+
+        let mut cursor = Point::zero();
+        for item in self.0.iter() {
+            match item {
+                PathItem::MoveTo(to) => cursor = *to,
+                PathItem::LineTo(to) => {
+                    include(cursor);
+                    include(*to);
+                    cursor = *to;
+                }
+                PathItem::CubicTo(c0, c1, end) => {
+                    let cubic = kurbo::CubicBez::new(
+                        kurbo::Point::new(cursor.x.to_pt(), cursor.y.to_pt()),
+                        kurbo::Point::new(c0.x.to_pt(), c0.y.to_pt()),
+                        kurbo::Point::new(c1.x.to_pt(), c1.y.to_pt()),
+                        kurbo::Point::new(end.x.to_pt(), end.y.to_pt()),
+                    );
+                    let bbox = cubic.bounding_box();
+                    include(Point::new(Abs::pt(bbox.x0), Abs::pt(bbox.y0)));
+                    include(Point::new(Abs::pt(bbox.x1), Abs::pt(bbox.y1)));
+                    cursor = *end;
+                }
+                PathItem::ClosePath => {}
+            }
+        }
+
+        if min.x > max.x {
+            return (Point::zero(), Size::zero());
+        }
+
+        (min, (max - min).to_size())
+    }
+
     /// Computes the size of bounding box of this path.
     pub fn bbox_size(&self) -> Size {
         let mut min_x = Abs::inf();
@@ -313,3 +639,216 @@ impl Path {
         Size::new(max_x - min_x, max_y - min_y)
     }
 }
+
+/// Parses SVG path data into a [`Path`].
+struct SvgPathParser<'a> {
+    /// The path data.
+    data: &'a str,
+    /// The byte position in the path data.
+    cursor: usize,
+    /// The resulting path.
+    path: Path,
+    /// The current point.
+    current: Point,
+    /// The start of the current subpath.
+    start: Point,
+    /// The previous command and its last control point, used to reflect the
+    /// control point for the shorthand `S` and `T` commands.
+    last: Option<(char, Point)>,
+}
+
+impl<'a> SvgPathParser<'a> {
+    fn new(data: &'a str) -> Self {
+        Self {
+            data,
+            cursor: 0,
+            path: Path::new(),
+            current: Point::zero(),
+            start: Point::zero(),
+            last: None,
+        }
+    }
+
+    /// Parse all commands.
+    fn parse(mut self) -> StrResult<Path> {
+        self.skip_separators();
+        let mut command = None;
+        while self.cursor < self.data.len() {
+            let at = self.cursor;
+            let c = self.peek().unwrap();
+            if c.is_ascii_alphabetic() {
+                self.cursor += 1;
+                command = Some((c, at));
+            } else if let Some((prev, _)) = command {
+                // Coordinates after the first pair of a move command are
+                // implicit line commands.
+                let next = match prev {
+                    'M' => 'L',
+                    'm' => 'l',
+                    'Z' | 'z' => bail!("expected command at position {at}, found `{c}`"),
+                    prev => prev,
+                };
+                command = Some((next, at));
+            } else {
+                bail!("path data must start with a move command");
+            }
+
+            let (c, at) = command.unwrap();
+            if self.path.0.is_empty() && !matches!(c, 'M' | 'm') {
+                bail!("path data must start with a move command");
+            }
+
+            self.command(c).map_err(|err| {
+                eco_format!("invalid `{c}` command at position {at}: {err}")
+            })?;
+            self.skip_separators();
+        }
+        Ok(self.path)
+    }
+
+    /// Parse the arguments of a single command and add it to the path.
+    fn command(&mut self, c: char) -> StrResult<()> {
+        let relative = c.is_ascii_lowercase();
+        let origin = if relative { self.current } else { Point::zero() };
+        let mut control = None;
+        match c.to_ascii_uppercase() {
+            'M' => {
+                let p = origin + self.point()?;
+                self.path.move_to(p);
+                self.current = p;
+                self.start = p;
+            }
+            'L' => {
+                let p = origin + self.point()?;
+                self.path.line_to(p);
+                self.current = p;
+            }
+            'H' => {
+                let x = self.number()? + origin.x;
+                self.current.x = x;
+                self.path.line_to(self.current);
+            }
+            'V' => {
+                let y = self.number()? + origin.y;
+                self.current.y = y;
+                self.path.line_to(self.current);
+            }
+            'C' => {
+                let c1 = origin + self.point()?;
+                let c2 = origin + self.point()?;
+                let p = origin + self.point()?;
+                self.path.cubic_to(c1, c2, p);
+                self.current = p;
+                control = Some(c2);
+            }
+            'S' => {
+                let c1 = self.reflected(&['C', 'S']);
+                let c2 = origin + self.point()?;
+                let p = origin + self.point()?;
+                self.path.cubic_to(c1, c2, p);
+                self.current = p;
+                control = Some(c2);
+            }
+            'Q' => {
+                let q = origin + self.point()?;
+                let p = origin + self.point()?;
+                self.quad_to(q, p);
+                control = Some(q);
+            }
+            'T' => {
+                let q = self.reflected(&['Q', 'T']);
+                let p = origin + self.point()?;
+                self.quad_to(q, p);
+                control = Some(q);
+            }
+            'Z' => {
+                self.path.close_path();
+                self.current = self.start;
+            }
+            'A' => bail!("elliptical arcs are not supported"),
+            _ => bail!("unknown command"),
+        }
+
+        self.last = control.map(|p| (c.to_ascii_uppercase(), p));
+        Ok(())
+    }
+
+    /// Add a quadratic curve as an equivalent cubic curve.
+    fn quad_to(&mut self, control: Point, to: Point) {
+        let from = self.current;
+        let c1 = from + (control - from) * (2.0 / 3.0);
+        let c2 = to + (control - to) * (2.0 / 3.0);
+        self.path.cubic_to(c1, c2, to);
+        self.current = to;
+    }
+
+    /// The reflection of the previous command's last control point if that
+    /// command is one of the given ones and otherwise the current point.
+    fn reflected(&self, commands: &[char]) -> Point {
+        match self.last {
+            Some((c, control)) if commands.contains(&c) => self.current * 2.0 - control,
+            _ => self.current,
+        }
+    }
+
+    /// Parse a coordinate pair.
+    fn point(&mut self) -> StrResult<Point> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok(Point::new(x, y))
+    }
+
+    /// Parse a single coordinate.
+    fn number(&mut self) -> StrResult<Abs> {
+        self.skip_separators();
+        let rest = &self.data[self.cursor..];
+        let mut len = 0;
+        let mut seen_dot = false;
+        let mut seen_exp = false;
+        for (i, c) in rest.char_indices() {
+            let valid = match c {
+                '+' | '-' => {
+                    i == 0 || matches!(rest[..i].chars().last(), Some('e' | 'E'))
+                }
+                '.' if !seen_dot && !seen_exp => {
+                    seen_dot = true;
+                    true
+                }
+                'e' | 'E' if !seen_exp && i > 0 => {
+                    seen_exp = true;
+                    true
+                }
+                c => c.is_ascii_digit(),
+            };
+            if !valid {
+                break;
+            }
+            len = i + c.len_utf8();
+        }
+
+        let number =
+            rest[..len].parse::<f64>().map_err(|_| match rest.chars().next() {
+                Some(c) => eco_format!("expected number, found `{c}`"),
+                None => "expected number, found end of data".into(),
+            })?;
+
+        if !number.is_finite() {
+            bail!("number `{}` is too large", &rest[..len]);
+        }
+
+        self.cursor += len;
+        Ok(Abs::pt(number))
+    }
+
+    /// The next character.
+    fn peek(&self) -> Option<char> {
+        self.data[self.cursor..].chars().next()
+    }
+
+    /// Skip whitespace and commas.
+    fn skip_separators(&mut self) {
+        while let Some(c) = self.peek().filter(|&c| c.is_whitespace() || c == ',') {
+            self.cursor += c.len_utf8();
+        }
+    }
+}
@@ -6,10 +6,11 @@ use crate::foundations::{
     elem, func, scope, Content, NativeElement, Resolve, Smart, StyleChain,
 };
 use crate::layout::{
-    Axes, Em, Fragment, Frame, FrameItem, Layout, Length, Point, Regions, Rel,
+    Axes, Em, Fragment, Frame, FrameItem, Layout, Length, Point, Ratio, Regions, Rel,
 };
+use crate::syntax::{Span, Spanned};
 use crate::util::Numeric;
-use crate::visualize::{FixedStroke, Geometry, Paint, Path, Shape, Stroke};
+use crate::visualize::{FillRule, FixedStroke, Geometry, Paint, Shape, Stroke};
 
 /// A closed polygon.
 ///
@@ -33,10 +34,25 @@ pub struct PolygonElem {
     /// When setting a fill, the default stroke disappears. To create a
     /// rectangle with both fill and stroke, you have to configure both.
     ///
-    /// Currently all polygons are filled according to the
-    /// [non-zero winding rule](https://en.wikipedia.org/wiki/Nonzero-rule).
+    /// Which regions of a self-intersecting polygon are filled is decided by
+    /// the [`fill-rule`]($polygon.fill-rule).
     pub fill: Option<Paint>,
 
+    /// How to fill the regions where the polygon's outline intersects itself.
+    ///
+    /// - `{"nonzero"}` fills every region the outline winds around, following
+    ///   the [non-zero winding rule](https://en.wikipedia.org/wiki/Nonzero-rule).
+    /// - `{"evenodd"}` leaves out every other nested region, following the
+    ///   [even-odd rule](https://en.wikipedia.org/wiki/Even-odd_rule).
+    ///
+    /// ```example
+    /// #let star = ((20pt, 0pt), (32pt, 36pt), (0pt, 14pt), (40pt, 14pt), (8pt, 36pt))
+    /// #polygon(fill: blue, ..star)
+    /// #polygon(fill: blue, fill-rule: "evenodd", ..star)
+    /// ```
+    #[default(FillRule::NonZero)]
+    pub fill_rule: FillRule,
+
     /// How to [stroke]($stroke) the polygon. This can be:
     ///
     /// Can be set to  `{none}` to disable the stroke or to `{auto}` for a
@@ -55,6 +71,11 @@ pub struct PolygonElem {
 impl PolygonElem {
     /// A regular polygon, defined by its size and number of vertices.
     ///
+    /// A regular polygon has no width or height of its own. It is sized by its
+    /// circumcircle instead. Other properties, like the
+    /// [fill rule]($polygon.fill-rule), can be configured with a set rule on
+    /// `polygon`.
+    ///
     /// ```example
     /// #polygon.regular(
     ///   fill: blue.lighten(80%),
@@ -81,35 +102,209 @@ impl PolygonElem {
         #[default(Em::one().into())]
         size: Length,
 
-        /// The number of vertices in the polygon.
+        /// The radius of the circumcircle of the regular polygon. This takes
+        /// precedence over `size` if both are given.
+        #[named]
+        radius: Option<Length>,
+
+        /// The number of vertices in the polygon. Must be at least three.
+        #[named]
+        #[default(Spanned::new(3, Span::detached()))]
+        vertices: Spanned<u64>,
+    ) -> SourceResult<Content> {
+        if vertices.v < 3 {
+            bail!(vertices.span, "regular polygon must have at least three vertices");
+        }
+
+        Ok(regular(fill, stroke, radius.unwrap_or(size / 2.0), vertices.v))
+    }
+
+    /// An equilateral triangle. This is a shorthand for a
+    /// [regular polygon]($polygon.regular) with three vertices and takes the
+    /// same arguments, except for the number of vertices.
+    ///
+    /// ```example
+    /// #polygon.triangle(size: 30pt, fill: red)
+    /// ```
+    #[func]
+    pub fn triangle(
+        /// How to fill the triangle. See the general
+        /// [polygon's documentation]($polygon.fill) for more details.
+        #[named]
+        fill: Option<Option<Paint>>,
+
+        /// How to stroke the triangle. See the general
+        /// [polygon's documentation]($polygon.stroke) for more details.
+        #[named]
+        stroke: Option<Smart<Option<Stroke>>>,
+
+        /// The diameter of the triangle's circumcircle.
+        #[named]
+        #[default(Em::one().into())]
+        size: Length,
+
+        /// The radius of the triangle's circumcircle. This takes precedence
+        /// over `size` if both are given.
         #[named]
-        #[default(3)]
-        vertices: u64,
+        radius: Option<Length>,
     ) -> Content {
-        let radius = size / 2.0;
-        let angle = |i: f64| {
-            2.0 * PI * i / (vertices as f64) + PI * (1.0 / 2.0 - 1.0 / vertices as f64)
-        };
-        let (horizontal_offset, vertical_offset) = (0..=vertices)
-            .map(|v| {
-                (
-                    (radius * angle(v as f64).cos()) + radius,
-                    (radius * angle(v as f64).sin()) + radius,
-                )
-            })
-            .fold((radius, radius), |(min_x, min_y), (v_x, v_y)| {
-                (
-                    if min_x < v_x { min_x } else { v_x },
-                    if min_y < v_y { min_y } else { v_y },
-                )
-            });
-        let vertices = (0..=vertices)
-            .map(|v| {
-                let x = (radius * angle(v as f64).cos()) + radius - horizontal_offset;
-                let y = (radius * angle(v as f64).sin()) + radius - vertical_offset;
-                Axes::new(x, y).map(Rel::from)
-            })
-            .collect();
+        regular(fill, stroke, radius.unwrap_or(size / 2.0), 3)
+    }
+
+    /// A regular pentagon. This is a shorthand for a
+    /// [regular polygon]($polygon.regular) with five vertices and takes the
+    /// same arguments, except for the number of vertices.
+    ///
+    /// ```example
+    /// #polygon.pentagon(size: 30pt, fill: green)
+    /// ```
+    #[func]
+    pub fn pentagon(
+        /// How to fill the pentagon. See the general
+        /// [polygon's documentation]($polygon.fill) for more details.
+        #[named]
+        fill: Option<Option<Paint>>,
+
+        /// How to stroke the pentagon. See the general
+        /// [polygon's documentation]($polygon.stroke) for more details.
+        #[named]
+        stroke: Option<Smart<Option<Stroke>>>,
+
+        /// The diameter of the pentagon's circumcircle.
+        #[named]
+        #[default(Em::one().into())]
+        size: Length,
+
+        /// The radius of the pentagon's circumcircle. This takes precedence
+        /// over `size` if both are given.
+        #[named]
+        radius: Option<Length>,
+    ) -> Content {
+        regular(fill, stroke, radius.unwrap_or(size / 2.0), 5)
+    }
+
+    /// A regular hexagon. This is a shorthand for a
+    /// [regular polygon]($polygon.regular) with six vertices and takes the
+    /// same arguments, except for the number of vertices.
+    ///
+    /// ```example
+    /// #polygon.hexagon(size: 30pt, fill: blue)
+    /// ```
+    #[func]
+    pub fn hexagon(
+        /// How to fill the hexagon. See the general
+        /// [polygon's documentation]($polygon.fill) for more details.
+        #[named]
+        fill: Option<Option<Paint>>,
+
+        /// How to stroke the hexagon. See the general
+        /// [polygon's documentation]($polygon.stroke) for more details.
+        #[named]
+        stroke: Option<Smart<Option<Stroke>>>,
+
+        /// The diameter of the hexagon's circumcircle.
+        #[named]
+        #[default(Em::one().into())]
+        size: Length,
+
+        /// The radius of the hexagon's circumcircle. This takes precedence
+        /// over `size` if both are given.
+        #[named]
+        radius: Option<Length>,
+    ) -> Content {
+        regular(fill, stroke, radius.unwrap_or(size / 2.0), 6)
+    }
+
+    /// A regular octagon. This is a shorthand for a
+    /// [regular polygon]($polygon.regular) with eight vertices and takes the
+    /// same arguments, except for the number of vertices.
+    ///
+    /// ```example
+    /// #polygon.octagon(size: 30pt, fill: orange)
+    /// ```
+    #[func]
+    pub fn octagon(
+        /// How to fill the octagon. See the general
+        /// [polygon's documentation]($polygon.fill) for more details.
+        #[named]
+        fill: Option<Option<Paint>>,
+
+        /// How to stroke the octagon. See the general
+        /// [polygon's documentation]($polygon.stroke) for more details.
+        #[named]
+        stroke: Option<Smart<Option<Stroke>>>,
+
+        /// The diameter of the octagon's circumcircle.
+        #[named]
+        #[default(Em::one().into())]
+        size: Length,
+
+        /// The radius of the octagon's circumcircle. This takes precedence
+        /// over `size` if both are given.
+        #[named]
+        radius: Option<Length>,
+    ) -> Content {
+        regular(fill, stroke, radius.unwrap_or(size / 2.0), 8)
+    }
+
+    /// A star-shaped polygon, defined by its size, its number of points and
+    /// the size of its inner vertices.
+    ///
+    /// The outer vertices lie on the circumcircle, while the inner vertices
+    /// lie on a smaller concentric circle. If both circles coincide, the star
+    /// degenerates into a regular polygon with twice as many vertices.
+    ///
+    /// ```example
+    /// #polygon.star(
+    ///   fill: yellow,
+    ///   stroke: orange,
+    ///   size: 30pt,
+    ///   points: 5,
+    /// )
+    /// ```
+    #[func]
+    pub fn star(
+        /// How to fill the star. See the general
+        /// [polygon's documentation]($polygon.fill) for more details.
+        #[named]
+        fill: Option<Option<Paint>>,
+
+        /// How to stroke the star. See the general
+        /// [polygon's documentation]($polygon.stroke) for more details.
+        #[named]
+        stroke: Option<Smart<Option<Stroke>>>,
+
+        /// The diameter of the circle on which the star's outer vertices lie.
+        #[named]
+        #[default(Em::one().into())]
+        size: Length,
+
+        /// The radius of the circle on which the star's outer vertices lie.
+        /// This takes precedence over `size` if both are given.
+        #[named]
+        radius: Option<Length>,
+
+        /// The radius of the circle on which the star's inner vertices lie,
+        /// relative to the outer radius. Must be between `{0%}` and `{100%}`.
+        #[named]
+        #[default(Spanned::new(Ratio::new(0.5), Span::detached()))]
+        inner: Spanned<Ratio>,
+
+        /// The number of points of the star. Must be at least three.
+        #[named]
+        #[default(Spanned::new(5, Span::detached()))]
+        points: Spanned<u64>,
+    ) -> SourceResult<Content> {
+        if points.v < 3 {
+            bail!(points.span, "star must have at least three points");
+        }
+
+        if inner.v.get() < 0.0 || inner.v.get() > 1.0 {
+            bail!(inner.span, "inner radius must be between 0% and 100%");
+        }
+
+        let outer = radius.unwrap_or(size / 2.0);
+        let vertices = radial_vertices(&[outer, inner.v.of(outer)], points.v);
 
         let mut elem = PolygonElem::new(vertices);
         if let Some(fill) = fill {
@@ -118,8 +313,59 @@ impl PolygonElem {
         if let Some(stroke) = stroke {
             elem.push_stroke(stroke);
         }
-        elem.pack()
+        Ok(elem.pack())
+    }
+}
+
+/// Creates a regular polygon with `n` vertices on a circle with the given
+/// radius.
+fn regular(
+    fill: Option<Option<Paint>>,
+    stroke: Option<Smart<Option<Stroke>>>,
+    radius: Length,
+    n: u64,
+) -> Content {
+    let mut elem = PolygonElem::new(radial_vertices(&[radius], n));
+    if let Some(fill) = fill {
+        elem.push_fill(fill);
+    }
+    if let Some(stroke) = stroke {
+        elem.push_stroke(stroke);
     }
+    elem.pack()
+}
+
+/// Computes the vertices of a polygon whose corners are distributed evenly on
+/// concentric circles, cycling through the given radii.
+///
+/// With a single radius, this yields a regular polygon with `n` vertices that
+/// rests on a flat edge. With two radii, it yields a star with `n` points. The
+/// resulting points are moved such that the polygon's bounding box starts at
+/// the origin.
+fn radial_vertices(radii: &[Length], n: u64) -> Vec<Axes<Rel<Length>>> {
+    let outer = radii[0];
+    let count = n * radii.len() as u64;
+    let angle =
+        |i: u64| 2.0 * PI * i as f64 / count as f64 + PI * (1.0 / 2.0 - 1.0 / n as f64);
+    let point = |i: u64| {
+        let radius = radii[i as usize % radii.len()];
+        ((radius * angle(i).cos()) + outer, (radius * angle(i).sin()) + outer)
+    };
+    let (horizontal_offset, vertical_offset) =
+        (0..=count)
+            .map(point)
+            .fold((outer, outer), |(min_x, min_y), (v_x, v_y)| {
+                (
+                    if min_x < v_x { min_x } else { v_x },
+                    if min_y < v_y { min_y } else { v_y },
+                )
+            });
+    (0..=count)
+        .map(|i| {
+            let (x, y) = point(i);
+            Axes::new(x - horizontal_offset, y - vertical_offset).map(Rel::from)
+        })
+        .collect()
 }
 
 impl Layout for PolygonElem {
@@ -152,21 +398,22 @@ impl Layout for PolygonElem {
 
         // Prepare fill and stroke.
         let fill = self.fill(styles);
-        let stroke = match self.stroke(styles) {
+        let stroke = self.stroke(styles);
+        if let Smart::Custom(Some(stroke)) = &stroke {
+            stroke.ensure_absolute(self.span())?;
+        }
+        let stroke = match stroke {
             Smart::Auto if fill.is_none() => Some(FixedStroke::default()),
             Smart::Auto => None,
             Smart::Custom(stroke) => stroke.map(Stroke::unwrap_or_default),
         };
 
-        // Construct a closed path given all points.
-        let mut path = Path::new();
-        path.move_to(points[0]);
-        for &point in &points[1..] {
-            path.line_to(point);
-        }
-        path.close_path();
-
-        let shape = Shape { geometry: Geometry::Path(path), stroke, fill };
+        let shape = Shape {
+            geometry: Geometry::Polygon(points),
+            stroke,
+            fill,
+            fill_rule: self.fill_rule(styles),
+        };
         frame.push(Point::zero(), FrameItem::Shape(shape, self.span()));
 
         Ok(Fragment::frame(frame))
@@ -0,0 +1,131 @@
+use crate::foundations::{cast, dict, Dict, Resolve, StyleChain};
+use crate::layout::{Abs, Length};
+use crate::util::Numeric;
+use crate::visualize::Color;
+
+/// The number of layers used to approximate a blurred shadow.
+const BLUR_LAYERS: usize = 8;
+
+/// A drop shadow behind a shape.
+///
+/// The shadow has the same outline as the shape, moved by `dx` and `dy`. A
+/// blurred shadow is approximated with several translucent layers of growing
+/// size, so that every export format can render it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Shadow<T: Numeric = Length> {
+    /// The shadow's color.
+    pub color: Color,
+    /// The horizontal offset of the shadow.
+    pub dx: T,
+    /// The vertical offset of the shadow.
+    pub dy: T,
+    /// How far the shadow's edge is blurred.
+    pub blur: T,
+}
+
+impl<T: Numeric> Shadow<T> {
+    /// Map the shadow's lengths with `f`.
+    pub fn map<F, U: Numeric>(self, f: F) -> Shadow<U>
+    where
+        F: Fn(T) -> U,
+    {
+        Shadow {
+            color: self.color,
+            dx: f(self.dx),
+            dy: f(self.dy),
+            blur: f(self.blur),
+        }
+    }
+}
+
+impl Shadow<Abs> {
+    /// The layers that make up the shadow, each given by how much it grows
+    /// beyond the shape's outline and its color.
+    ///
+    /// See [`blur_layers`] for how the layers' opacities are chosen.
+    pub fn layers(&self) -> Vec<(Abs, Color)> {
+        if self.blur <= Abs::zero() {
+            return vec![(Abs::zero(), self.color)];
+        }
+
+        let color = match self.color.alpha() {
+            Some(_) => self.color,
+            None => self.color.to_rgb(),
+        };
+
+        blur_layers(self.blur, color.alpha().unwrap_or(1.0))
+            .into_iter()
+            .map(|(grow, alpha)| (grow, color.with_alpha(alpha)))
+            .collect()
+    }
+}
+
+/// The layers that approximate an edge blurred across `blur`, each given by
+/// how much it grows beyond the outline and its alpha.
+///
+/// The alphas are chosen such that the opacity ramps up linearly across the
+/// blurred edge and composites to `alpha` where all layers overlap.
+pub(crate) fn blur_layers(blur: Abs, alpha: f32) -> Vec<(Abs, f32)> {
+    let n = BLUR_LAYERS as f64;
+    let mut covered = 0.0;
+    (1..=BLUR_LAYERS)
+        .map(|k| {
+            let grow = blur * (0.5 - (k as f64 - 0.5) / n);
+            let target = alpha * k as f32 / BLUR_LAYERS as f32;
+            let layer = (target - covered) / (1.0 - covered);
+            covered = target;
+            (grow, layer)
+        })
+        .collect()
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            dx: Length::zero(),
+            dy: Length::zero(),
+            blur: Length::zero(),
+        }
+    }
+}
+
+impl Resolve for Shadow {
+    type Output = Shadow<Abs>;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        self.map(|length| length.resolve(styles))
+    }
+}
+
+cast! {
+    Shadow,
+    self => dict! {
+        "color" => self.color,
+        "dx" => self.dx,
+        "dy" => self.dy,
+        "blur" => self.blur,
+    }.into_value(),
+    mut dict: Dict => {
+        let mut shadow = Shadow::default();
+        if let Ok(color) = dict.take("color") {
+            shadow.color = color.cast()?;
+        }
+        if let Ok(dx) = dict.take("dx") {
+            shadow.dx = dx.cast()?;
+        }
+        if let Ok(dy) = dict.take("dy") {
+            shadow.dy = dy.cast()?;
+        }
+        if let Ok(blur) = dict.take("blur") {
+            shadow.blur = blur.cast()?;
+        }
+        dict.finish(&["color", "dx", "dy", "blur"])?;
+        shadow
+    },
+}
+
+cast! {
+    Shadow<Abs>,
+    self => self.map(Length::from).into_value(),
+}
@@ -1,15 +1,29 @@
-use std::f64::consts::SQRT_2;
+use std::f64::consts::{FRAC_PI_2, PI, SQRT_2};
 
-use crate::diag::SourceResult;
+use comemo::TrackedMut;
+use kurbo::{ParamCurveNearest, Shape as _};
+
+use crate::diag::{bail, warning, At, SourceResult, StrResult};
 use crate::engine::Engine;
-use crate::foundations::{elem, Content, NativeElement, Resolve, Smart, StyleChain};
+use crate::foundations::{
+    cast, dict, elem, func, scope, Array, Cast, Content, Dict, Fold, Func, NativeElement,
+    Resolve, Smart, StyleChain, Styles, Value,
+};
+use crate::introspection::{Locator, Meta, MetaElem};
 use crate::layout::{
-    Abs, Axes, Corner, Corners, Fragment, Frame, FrameItem, Layout, Length, Point, Ratio,
-    Regions, Rel, Sides, Size,
+    layout_breakable, Abs, Align, Angle, Axes, Corner, Corners, DirSides, Fragment,
+    Frame, FrameItem, GroupItem, Layout, Length, Point, Ratio, Regions, Rel, Sides, Size,
+    Sizing, Transform,
 };
-use crate::syntax::Span;
-use crate::util::Get;
-use crate::visualize::{FixedStroke, Paint, Path, Stroke};
+use crate::model::ParElem;
+use crate::syntax::{Span, Spanned};
+use crate::text::{families, variant, TextElem};
+use crate::util::{Get, Numeric, Scalar};
+use crate::visualize::{
+    blur_layers, Color, FixedStroke, ImageElem, Paint, Path, PathItem, RelativeTo,
+    Shadow, Stroke, ThemePaint,
+};
+use crate::World;
 
 /// A rectangle with optional content.
 ///
@@ -24,27 +38,191 @@ use crate::visualize::{FixedStroke, Paint, Path, Stroke};
 ///   to fit the content.
 /// ]
 /// ```
-#[elem(title = "Rectangle", Layout)]
+#[elem(scope, title = "Rectangle", Layout, BasicShape)]
 pub struct RectElem {
     /// The rectangle's width, relative to its parent container.
-    pub width: Smart<Rel<Length>>,
+    ///
+    /// A fractional width like `{1fr}` takes up the remaining space of the
+    /// region the rectangle is placed in, for example a grid cell or a block
+    /// with a fixed size. The same holds for the height. The fraction itself
+    /// only matters to containers that distribute space between their
+    /// children, like a [box]($box.width) in a paragraph.
+    ///
+    /// ```example
+    /// #block(width: 120pt, height: 50pt, stroke: 0.5pt)[
+    ///   #rect(width: 50%, height: 1fr, fill: aqua)
+    /// ]
+    /// ```
+    pub width: Sizing,
 
     /// The rectangle's height, relative to its parent container.
-    pub height: Smart<Rel<Length>>,
+    pub height: Sizing,
+
+    /// The rectangle's minimum width, relative to its parent container.
+    ///
+    /// Together with the other size limits, this lets a rectangle fit its
+    /// content while staying within bounds. The limits also take precedence
+    /// over a fixed `width` or `height` and over the `aspect` ratio.
+    ///
+    /// ```example
+    /// #set rect(min-width: 50pt, max-width: 90pt)
+    /// #rect[Short]
+    /// #rect[A bit too long to fit]
+    /// ```
+    pub min_width: Option<Rel<Length>>,
+
+    /// The rectangle's maximum width, relative to its parent container.
+    pub max_width: Option<Rel<Length>>,
+
+    /// The rectangle's minimum height, relative to its parent container.
+    pub min_height: Option<Rel<Length>>,
+
+    /// The rectangle's maximum height, relative to its parent container.
+    pub max_height: Option<Rel<Length>>,
+
+    /// Whether the rectangle fills the available space along the axes for
+    /// which no `width` or `height` is set.
+    ///
+    /// - `{auto}`: Fill the space if the container asks its children to do so
+    ///   and otherwise fit the content.
+    /// - `{true}`: Always fill the remaining space of the region, like a
+    ///   [fractional size]($rect.width).
+    /// - `{false}`: Always fit the content.
+    ///
+    /// ```example
+    /// #rect(grow: true, fill: aqua)[Full width]
+    /// #rect(grow: false, fill: aqua)[Hugging]
+    /// ```
+    pub grow: Smart<bool>,
+
+    /// The rectangle's aspect ratio, that is, its width divided by its height.
+    ///
+    /// If only one of `width` and `height` is set, the other one is derived
+    /// from it. If neither is set, the rectangle grows to fit its content
+    /// while keeping the aspect ratio. If both are set, this has no effect.
+    ///
+    /// ```example
+    /// #rect(width: 60pt, aspect: 16 / 9)
+    /// #rect(aspect: 3)[Wide]
+    /// ```
+    pub aspect: Option<Scalar>,
+
+    /// Whether the rectangle and its content can be broken across pages and
+    /// columns when it is placed directly into the flow of the document.
+    ///
+    /// Each part of a broken rectangle is filled and stroked separately. Only
+    /// the top corners of the first part and the bottom corners of the last
+    /// part are rounded. This has no effect if an `aspect` ratio is set.
+    /// Squares, ellipses, and circles are never broken.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #rect(
+    ///   breakable: true,
+    ///   radius: 6pt,
+    ///   fill: aqua,
+    ///   lorem(30),
+    /// )
+    /// ```
+    #[default(false)]
+    pub breakable: bool,
 
     /// How to fill the rectangle.
     ///
     /// When setting a fill, the default stroke disappears. To create a
     /// rectangle with both fill and stroke, you have to configure both.
     ///
+    /// Besides a paint, the fill can also be an [image]($image), which is
+    /// scaled to the rectangle's size according to its `fit` and clipped to
+    /// the rectangle's outline, including its rounded corners. Image fills are
+    /// not affected by the rectangle's `opacity`.
+    ///
     /// ```example
     /// #rect(fill: blue)
+    /// #rect(
+    ///   width: 40pt,
+    ///   height: 40pt,
+    ///   radius: 50%,
+    ///   fill: image("tiger.jpg", fit: "cover"),
+    /// )
     /// ```
-    pub fill: Option<Paint>,
+    ///
+    /// A [gradient]($gradient) fill spans the rectangle by default. When the
+    /// gradient is created with `{relative: "parent"}`, it instead spans the
+    /// rectangle's [parent container]($gradient/#relativeness), so that
+    /// several rectangles in the same container share one continuous
+    /// gradient.
+    ///
+    /// ```example
+    /// #let grad = gradient.linear(red, blue, relative: "parent")
+    /// #block(width: 100%, stack(
+    ///   dir: ltr,
+    ///   spacing: 4pt,
+    ///   ..range(4).map(_ => rect(width: 40pt, fill: grad)),
+    /// ))
+    /// ```
+    ///
+    /// The fill can also be a [function]($function) that receives the
+    /// rectangle's final size as a dictionary with the keys `width` and
+    /// `height` and returns a paint, an image, or `{none}`. If the rectangle
+    /// is broken across pages, the function is called for each part.
+    ///
+    /// ```example
+    /// #let warn(size) = if size.width > 2cm { red } else { green }
+    /// #rect(fill: warn)[Short]
+    /// #rect(fill: warn)[A much longer text]
+    /// ```
+    ///
+    /// An array of paints is painted from bottom to top, which is handy to
+    /// tint a base color with a translucent overlay. Only the bottom paint is
+    /// blurred.
+    ///
+    /// ```example
+    /// #rect(fill: (blue, rgb(255, 255, 255, 40%)))[Badge]
+    /// ```
+    ///
+    /// A [paint of the theme]($theme.paint) is looked up by name in the
+    /// active [theme]($theme) when the rectangle is laid out. This works for
+    /// the whole fill only, not for single layers or the stroke.
+    ///
+    /// ```example
+    /// #set theme(paints: (primary: eastern))
+    /// #rect(fill: theme.paint("primary"))[Themed]
+    /// ```
+    ///
+    /// Colors keep their color space, so a fill given in [`cmyk`]($color.cmyk)
+    /// ends up as CMYK in the exported PDF, which suits print. This also holds
+    /// for strokes and for gradients, which can interpolate in a perceptual
+    /// space like [`oklch`]($color.oklch).
+    ///
+    /// ```example
+    /// #rect(fill: cmyk(0%, 60%, 100%, 0%), stroke: cmyk(0%, 0%, 0%, 100%))
+    /// #rect(fill: gradient.linear(red, blue, space: oklch))
+    /// ```
+    ///
+    /// If set to `{auto}`, the rectangle is filled with the current
+    /// [text color]($text.fill). This is handy for icon-like shapes that should
+    /// follow the surrounding text.
+    ///
+    /// ```example
+    /// #set text(fill: blue)
+    /// #let dot = box(circle(radius: 3pt, fill: auto))
+    /// #dot Status: online
+    /// ```
+    #[default(Smart::Custom(None))]
+    pub fill: Smart<Option<Fill>>,
+
+    /// An alternative fill for when the pointer hovers over the rectangle.
+    ///
+    /// Static export formats like PDF and PNG always show the regular
+    /// [fill]($rect.fill). The alternative fill is only stored in the frame's
+    /// metadata, covering the rectangle's area, so that an interactive
+    /// export can swap the fills.
+    pub fill_hover: Option<Paint>,
 
     /// How to stroke the rectangle. This can be:
     ///
-    /// - `{none}` to disable stroking
+    /// - `{none}` to disable stroking, even if a stroke was set by a set rule
     /// - `{auto}` for a stroke of `{1pt + black}` if and if only if no fill is
     ///   given.
     /// - Any kind of [stroke]($stroke)
@@ -59,6 +237,15 @@ pub struct RectElem {
     ///   - `rest`: The stroke on all sides except those for which the
     ///     dictionary explicitly sets a size.
     ///
+    ///   Additionally, the keys `start` and `end` set the stroke of the side
+    ///   where text starts and ends, respectively. They resolve to the left or
+    ///   right side depending on the [text direction]($text.dir) and take
+    ///   precedence over `left`, `right`, `x`, and `rest`.
+    ///
+    ///   Each side's stroke is folded with the strokes from set rules on its
+    ///   own, so a side can, for instance, have its own dash pattern while
+    ///   keeping the paint and thickness from a set rule.
+    ///
     /// ```example
     /// #stack(
     ///   dir: ltr,
@@ -70,13 +257,27 @@ pub struct RectElem {
     /// ```
     #[resolve]
     #[fold]
-    pub stroke: Smart<Sides<Option<Option<Stroke>>>>,
+    pub stroke: Smart<DirSides<Option<Option<Stroke>>>>,
+
+    /// Whether to draw the stroke on top of the rectangle's content instead of
+    /// behind it.
+    ///
+    /// This is useful for a border that overlaps its content, like an inset
+    /// frame around an image. The fill always stays behind the content.
+    ///
+    /// ```example
+    /// #rect(inset: 0pt, stroke: 4pt + red, stroke-over: true)[
+    ///   #rect(width: 60pt, height: 20pt, fill: aqua)
+    /// ]
+    /// ```
+    #[default(false)]
+    pub stroke_over: bool,
 
-    /// How much to round the rectangle's corners, relative to the minimum of
-    /// the width and height divided by two. This can be:
+    /// How much to round the rectangle's corners, relative to the shorter of
+    /// its width and height, so that `{50%}` makes a pill. This can be:
     ///
     /// - A relative length for a uniform corner radius.
-    /// - A dictionary: With a dictionary, the stroke for each side can be set
+    /// - A dictionary: With a dictionary, the radius for each corner can be set
     ///   individually. The dictionary can contain the following keys in order
     ///   of precedence:
     ///   - `top-left`: The top-left corner radius.
@@ -90,6 +291,15 @@ pub struct RectElem {
     ///   - `rest`: The radii for all corners except those for which the
     ///     dictionary explicitly sets a size.
     ///
+    /// Each radius can also be a dictionary with `x` and `y` keys to make the
+    /// corner elliptical. In this case, the horizontal radius is relative to
+    /// the width and the vertical radius relative to the height.
+    ///
+    /// If the radii of two adjacent corners don't fit along their shared side,
+    /// all radii are scaled down by the same factor until they do, like CSS
+    /// does for `border-radius`. This keeps the proportions between the
+    /// corners intact.
+    ///
     /// ```example
     /// #set rect(stroke: 4pt)
     /// #rect(
@@ -108,29 +318,365 @@ pub struct RectElem {
     /// ```
     #[resolve]
     #[fold]
-    pub radius: Corners<Option<Rel<Length>>>,
+    pub radius: Corners<Option<Radius<Rel<Length>>>>,
 
-    /// How much to pad the rectangle's content.
-    /// See the [box's documentation]($box.outset) for more details.
+    /// Whether to round the rectangle's corners without picking a radius.
+    ///
+    /// The corners then get a radius of an eighth of the rectangle's shorter
+    /// side. A corner with an explicit, non-zero `radius` keeps it.
+    ///
+    /// ```example
+    /// #rect(width: 80pt, height: 40pt, rounded: true)
+    /// ```
+    #[default(false)]
+    pub rounded: bool,
+
+    /// How much to smooth the rounded corners into a superellipse, between
+    /// `{0.0}` for circular or elliptical corners and `{1.0}` for a full
+    /// "squircle".
+    ///
+    /// Smoothed corners blend more gradually into the straight sides. This
+    /// has no effect on sharp corners.
+    ///
+    /// ```example
+    /// #set rect(width: 40pt, height: 40pt, radius: 15pt, fill: teal)
+    /// #stack(
+    ///   dir: ltr,
+    ///   spacing: 8pt,
+    ///   rect(),
+    ///   rect(smoothing: 0.5),
+    ///   rect(smoothing: 1.0),
+    /// )
+    /// ```
+    #[default(Scalar::ZERO)]
+    pub smoothing: Scalar,
+
+    /// How much to pad the rectangle's content. See the
+    /// [box's documentation]($box.inset) for more details.
+    ///
+    /// A relative inset is relative to the rectangle's own size rather than
+    /// to the size of its container. The horizontal sides are relative to the
+    /// width and the vertical sides to the height, so a rectangle that is
+    /// `{100pt}` wide and `{40pt}` high with an inset of `{10%}` leaves a
+    /// content area of `{80pt}` by `{32pt}`.
+    ///
+    /// ```example
+    /// #rect(width: 100%, inset: 10%, fill: aqua)[
+    ///   #rect(width: 100%, fill: white)
+    /// ]
+    /// ```
+    ///
+    /// A negative inset lets the content overflow the rectangle, for example
+    /// to make an image bleed out of its frame. The rectangle then shrinks by
+    /// the overflow, but never below zero.
+    ///
+    /// Like the [stroke]($rect.stroke), the inset and outset also accept the
+    /// keys `start` and `end`, which follow the [text direction]($text.dir).
+    ///
+    /// ```example
+    /// #set text(dir: rtl)
+    /// #rect(inset: (start: 20pt), stroke: (start: 2pt + red))[
+    ///   Text
+    /// ]
+    /// ```
     #[resolve]
     #[fold]
     #[default(Sides::splat(Abs::pt(5.0).into()))]
-    pub inset: Sides<Option<Rel<Length>>>,
+    pub inset: DirSides<Option<Rel<Length>>>,
+
+    /// How to align the rectangle's content within its content box, that is,
+    /// the rectangle's area without the [inset]($rect.inset).
+    ///
+    /// When `{auto}`, the content is aligned like the surrounding content,
+    /// which by default places it at the top left.
+    ///
+    /// ```example
+    /// #rect(width: 80pt, height: 40pt, align: center + horizon)[Centered]
+    /// #circle(radius: 25pt, align: center + horizon)[Label]
+    /// ```
+    pub align: Smart<Align>,
 
     /// How much to expand the rectangle's size without affecting the layout.
     /// See the [box's documentation]($box.outset) for more details.
     #[resolve]
     #[fold]
-    pub outset: Sides<Option<Rel<Length>>>,
+    pub outset: DirSides<Option<Rel<Length>>>,
+
+    /// A drop shadow behind the rectangle. This is a dictionary with the
+    /// following keys, all of which are optional:
+    ///
+    /// - `color`: The shadow's [color]($color). Defaults to black.
+    /// - `dx`: The horizontal offset of the shadow. Defaults to `{0pt}`.
+    /// - `dy`: The vertical offset of the shadow. Defaults to `{0pt}`.
+    /// - `blur`: How far the shadow's edge is blurred. Defaults to `{0pt}`,
+    ///   which produces a hard shadow.
+    ///
+    /// ```example
+    /// #set rect(fill: white, stroke: 1pt)
+    /// #stack(
+    ///   dir: ltr,
+    ///   spacing: 1em,
+    ///   rect(shadow: (dx: 3pt, dy: 3pt, color: gray)),
+    ///   rect(shadow: (dy: 2pt, blur: 6pt, color: rgb(0, 0, 0, 40%))),
+    /// )
+    /// ```
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
+    /// How far the edge of the rectangle's fill is blurred, for example to
+    /// create a glow.
+    ///
+    /// Like a blurred [shadow]($rect.shadow), the blur is approximated with
+    /// several translucent layers, half of it extending beyond the rectangle's
+    /// outline. The stroke and pattern fills are not blurred.
+    ///
+    /// ```example
+    /// #rect(fill: orange, blur: 8pt, radius: 50%)
+    /// ```
+    #[resolve]
+    pub blur: Length,
+
+    /// How opaque the rectangle's fill, stroke, and shadow are. At `{0%}`,
+    /// the rectangle is invisible.
+    ///
+    /// Overlapping translucent shapes blend with each other. Pattern and image
+    /// fills are not affected by the opacity and are painted fully opaque with
+    /// a warning.
+    ///
+    /// ```example
+    /// #set rect(width: 40pt, height: 25pt, fill: blue, opacity: 50%)
+    /// #rect()
+    /// #place(dx: 20pt, dy: -20pt, rect(fill: red))
+    /// ```
+    #[default(Ratio::one())]
+    pub opacity: Ratio,
+
+    /// Whether to clip the content inside the rectangle to its outline,
+    /// including rounded corners.
+    ///
+    /// The clip follows the inner edge of the stroke, not the content box
+    /// within the [inset]($rect.inset). Content that overflows the inset is
+    /// thus visible up to the stroke and cut off along the rounded corners.
+    ///
+    /// Links in the clipped content are clipped as well, so that they only
+    /// react within the visible area.
+    ///
+    /// ```example
+    /// #rect(
+    ///   width: 50pt,
+    ///   height: 30pt,
+    ///   inset: 0pt,
+    ///   radius: 10pt,
+    ///   clip: true,
+    ///   image("tiger.jpg", width: 100pt),
+    /// )
+    /// ```
+    #[default(false)]
+    pub clip: bool,
+
+    /// Whether a [link]($link) on the rectangle only reacts within its
+    /// outline, including rounded corners, instead of its whole bounding box.
+    ///
+    /// The link's area is approximated with thin horizontal strips that fit
+    /// into the rectangle.
+    ///
+    /// ```example
+    /// #link(
+    ///   "https://typst.app",
+    ///   rect(radius: 50%, fill: aqua, link-shape: true)[Click],
+    /// )
+    /// ```
+    #[default(false)]
+    pub link_shape: bool,
+
+    /// How much to rotate the rectangle clockwise around its center.
+    ///
+    /// This rotates the whole rectangle, that is, its fill, stroke, and
+    /// content together. To only turn the content within a rectangle that
+    /// stays in place, use [`content-rotate`]($rect.content-rotate) instead.
+    ///
+    /// In contrast to the [`rotate`]($rotate) function, the rectangle's
+    /// frame grows to fit the rotated rectangle, so that it doesn't overlap
+    /// surrounding content.
+    ///
+    /// ```example
+    /// #set rect(width: 30pt, height: 15pt)
+    /// A #rect(rotate: 30deg) B
+    /// ```
+    pub rotate: Angle,
+
+    /// How much to rotate the rectangle's content clockwise around the
+    /// rectangle's center.
+    ///
+    /// The rectangle itself stays in place and keeps its size, so the
+    /// rotated content may stick out of it. Combine this with
+    /// [`clip`]($rect.clip) to cut it off at the outline. Both rotations can
+    /// be combined: The content is rotated within the rectangle first and
+    /// then the whole rectangle is rotated.
+    ///
+    /// ```example
+    /// #set rect(width: 50pt, height: 50pt, inset: 0pt)
+    /// #let body = align(center + horizon)[Text]
+    /// #rect(rotate: 45deg, body)
+    /// #rect(content-rotate: 45deg, body)
+    /// ```
+    pub content_rotate: Angle,
+
+    /// How much to skew the rectangle horizontally around its center.
+    ///
+    /// Positive angles slant the rectangle's vertical sides to the left at
+    /// the top, which turns it into a parallelogram. Like for the
+    /// [rotation]($rect.rotate), the rectangle's frame grows to fit the
+    /// skewed rectangle. The angle must be between `{-90deg}` and `{90deg}`.
+    ///
+    /// ```example
+    /// #rect(skew: -20deg, fill: aqua)[Slanted]
+    /// ```
+    pub skew: Angle,
+
+    /// An amount to shift the rectangle's baseline by when it is placed
+    /// inline, for example in a [box]($box). Relative amounts are relative to
+    /// the rectangle's height.
+    ///
+    /// A positive amount moves the rectangle down. If set to `{auto}`, the
+    /// rectangle is vertically centered on the x-height of the surrounding
+    /// text, which suits small badges and icons.
+    ///
+    /// ```example
+    /// #set rect(width: 6pt, height: 6pt, inset: 0pt)
+    /// Default #box(rect()) \
+    /// Shifted #box(rect(baseline: 2pt)) \
+    /// Centered #box(rect(baseline: auto))
+    /// ```
+    #[resolve]
+    #[default(Smart::Custom(Rel::zero()))]
+    pub baseline: Smart<Rel<Length>>,
+
+    /// Whether to round the rectangle's height up to a multiple of a
+    /// baseline grid, so that it keeps the text after it on the line grid.
+    ///
+    /// - If `{false}`, the height is not rounded.
+    /// - If `{true}`, the grid unit is the distance between the baselines of
+    ///   two lines of a paragraph, that is, the height of a line from its
+    ///   [top edge]($text.top-edge) to its [bottom edge]($text.bottom-edge)
+    ///   plus the paragraph's [leading]($par.leading).
+    /// - If a length, it is the grid unit.
+    ///
+    /// The height is rounded last, so it may exceed the rectangle's
+    /// [maximum height]($rect.max-height).
+    ///
+    /// ```example
+    /// #set rect(inset: 4pt, snap-height: true)
+    /// #rect(height: 20pt)
+    /// #rect(height: 34pt)[Snapped]
+    /// ```
+    #[resolve]
+    pub snap_height: Snap,
+
+    /// The size that the rectangle takes on if it has no body, given as an
+    /// array of a width and a height. A set `width` or `height` takes
+    /// precedence and the size is limited to the available space.
+    ///
+    /// ```example
+    /// #set rect(default-size: (20pt, 10pt))
+    /// #rect() #rect(width: 40pt)
+    /// ```
+    #[resolve]
+    #[default(Axes::new(Abs::pt(45.0).into(), Abs::pt(30.0).into()))]
+    pub default_size: Axes<Length>,
 
     /// The content to place into the rectangle.
     ///
-    /// When this is omitted, the rectangle takes on a default size of at most
-    /// `{45pt}` by `{30pt}`.
+    /// When this is omitted, the rectangle takes on its
+    /// [default size]($rect.default-size).
     #[positional]
     pub body: Option<Content>,
 }
 
+#[scope]
+impl RectElem {
+    /// A rectangle that only outlines its content.
+    ///
+    /// This is a shorthand for a rectangle without inset and fill and with the
+    /// same stroke on all sides, regardless of any set rules for rectangles.
+    ///
+    /// ```example
+    /// Some #box(rect.outline[boxed]) text.
+    /// #rect.outline(stroke: 2pt + red)[Warning]
+    /// ```
+    #[func]
+    pub fn outline(
+        /// How to stroke the outline. A missing paint or thickness defaults
+        /// to a `{1pt}` black stroke.
+        #[named]
+        #[default]
+        stroke: Stroke,
+
+        /// The content to outline.
+        body: Content,
+    ) -> Content {
+        // Fix paint and thickness so that they don't fold with set rules.
+        let stroke = Stroke {
+            paint: Smart::Custom(stroke.paint.unwrap_or(Color::BLACK.into())),
+            thickness: Smart::Custom(stroke.thickness.unwrap_or(Abs::pt(1.0).into())),
+            ..stroke
+        };
+        RectElem::new()
+            .with_body(Some(body))
+            .with_inset(Sides::splat(Some(Rel::zero())).into())
+            .with_fill(Smart::Custom(None))
+            .with_stroke(Smart::Custom(Sides::splat(Some(Some(stroke))).into()))
+            .pack()
+    }
+
+    /// Measures the size of a rectangle around the given content.
+    ///
+    /// This lays out the rectangle like [`measure`]($measure) would, with the
+    /// active set rules for rectangles, but without filling or stroking it.
+    /// The result is the content's natural size plus the inset, which is
+    /// useful to give several rectangles a common size.
+    ///
+    /// ```example
+    /// #let buttons(..labels) = style(styles => {
+    ///   let labels = labels.pos()
+    ///   let width = calc.max(
+    ///     ..labels.map(it => rect.measure(it, styles).width)
+    ///   )
+    ///   for label in labels {
+    ///     box(rect(width: width, fill: aqua, label))
+    ///   }
+    /// })
+    ///
+    /// #buttons[Ok][Cancel][Apply all]
+    /// ```
+    ///
+    /// The returned dictionary has the entries `width` and `height`, both of
+    /// type [`length`]($length).
+    #[func]
+    pub fn measure(
+        /// The engine.
+        engine: &mut Engine,
+        /// The content of the rectangle.
+        body: Content,
+        /// The styles with which to layout the rectangle.
+        styles: Styles,
+    ) -> SourceResult<Dict> {
+        let styles = StyleChain::new(&styles);
+        let elem = RectElem::new().with_body(Some(body));
+        let pod = Regions::one(Axes::splat(Abs::inf()), Axes::splat(false));
+        let frames = measure_frames(
+            engine,
+            styles,
+            pod,
+            &elem.body(styles),
+            &elem.shape_style(styles),
+            elem.span(),
+        )?;
+        let Size { x, y } = frames.first().map_or(Size::zero(), Frame::size);
+        Ok(dict! { "width" => x, "height" => y })
+    }
+}
+
 impl Layout for RectElem {
     #[tracing::instrument(name = "RectElem::layout", skip_all)]
     fn layout(
@@ -139,20 +685,44 @@ impl Layout for RectElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        layout(
-            engine,
-            styles,
-            regions,
-            ShapeKind::Rect,
-            &self.body(styles),
-            Axes::new(self.width(styles), self.height(styles)),
-            self.fill(styles),
-            self.stroke(styles),
-            self.inset(styles),
-            self.outset(styles),
-            self.radius(styles),
-            self.span(),
-        )
+        let style = self.shape_style(styles);
+        layout(engine, styles, regions, &self.body(styles), style, self.span())
+    }
+}
+
+impl RectElem {
+    /// The properties that determine the rectangle's size and look.
+    fn shape_style(&self, styles: StyleChain) -> ShapeStyle {
+        ShapeStyle {
+            kind: self.kind(),
+            default: self.default_size(styles),
+            sizing: Axes::new(self.width(styles), self.height(styles)),
+            min: Axes::new(self.min_width(styles), self.min_height(styles)),
+            max: Axes::new(self.max_width(styles), self.max_height(styles)),
+            grow: self.grow(styles),
+            aspect: self.aspect(styles),
+            breakable: self.breakable(styles),
+            fill: self.fill(styles),
+            fill_hover: self.fill_hover(styles),
+            stroke: self.stroke(styles),
+            stroke_over: self.stroke_over(styles),
+            inset: self.inset(styles),
+            outset: self.outset(styles),
+            radius: rounded(self.radius(styles), self.rounded(styles)),
+            hole: None,
+            smoothing: self.smoothing(styles).get(),
+            shadow: self.shadow(styles),
+            blur: self.blur(styles),
+            opacity: self.opacity(styles),
+            clip: self.clip(styles),
+            link_shape: self.link_shape(styles),
+            align: self.align(styles),
+            rotate: self.rotate(styles),
+            content_rotate: self.content_rotate(styles),
+            skew: self.skew(styles),
+            baseline: self.baseline(styles),
+            snap_height: self.snap_height(styles),
+        }
     }
 }
 
@@ -169,7 +739,7 @@ impl Layout for RectElem {
 ///   sized to fit.
 /// ]
 /// ```
-#[elem(Layout)]
+#[elem(Layout, BasicShape)]
 pub struct SquareElem {
     /// The square's side length. This is mutually exclusive with `width` and
     /// `height`.
@@ -179,15 +749,17 @@ pub struct SquareElem {
     /// The square's width. This is mutually exclusive with `size` and `height`.
     ///
     /// In contrast to `size`, this can be relative to the parent container's
-    /// width.
+    /// width or [fractional]($rect.width).
     #[parse(
-        let size = args.named::<Smart<Length>>("size")?.map(|s| s.map(Rel::from));
+        let size = args
+            .named::<Smart<Length>>("size")?
+            .map(|s| s.map_or(Sizing::Auto, |s| Sizing::Rel(s.into())));
         match size {
             None => args.named("width")?,
             size => size,
         }
     )]
-    pub width: Smart<Rel<Length>>,
+    pub width: Sizing,
 
     /// The square's height. This is mutually exclusive with `size` and `width`.
     ///
@@ -197,42 +769,141 @@ pub struct SquareElem {
         None => args.named("height")?,
         size => size,
     })]
-    pub height: Smart<Rel<Length>>,
+    pub height: Sizing,
+
+    /// The square's minimum width. See the
+    /// [rectangle's documentation]($rect.min-width) for more details.
+    ///
+    /// The limits of both axes apply to the square's common side length.
+    pub min_width: Option<Rel<Length>>,
+
+    /// The square's maximum width.
+    pub max_width: Option<Rel<Length>>,
+
+    /// The square's minimum height.
+    pub min_height: Option<Rel<Length>>,
+
+    /// The square's maximum height.
+    pub max_height: Option<Rel<Length>>,
 
     /// How to fill the square. See the [rectangle's documentation]($rect.fill)
     /// for more details.
-    pub fill: Option<Paint>,
+    #[default(Smart::Custom(None))]
+    pub fill: Smart<Option<Fill>>,
+
+    /// An alternative fill for when the pointer hovers over the square. See
+    /// the [rectangle's documentation]($rect.fill-hover) for more details.
+    pub fill_hover: Option<Paint>,
 
     /// How to stroke the square. See the
     /// [rectangle's documentation]($rect.stroke) for more details.
     #[resolve]
     #[fold]
-    pub stroke: Smart<Sides<Option<Option<Stroke>>>>,
+    pub stroke: Smart<DirSides<Option<Option<Stroke>>>>,
+
+    /// Whether to draw the stroke on top of the square's content instead of
+    /// behind it. See the [rectangle's documentation]($rect.stroke-over) for
+    /// more details.
+    #[default(false)]
+    pub stroke_over: bool,
 
     /// How much to round the square's corners. See the
     /// [rectangle's documentation]($rect.radius) for more details.
     #[resolve]
     #[fold]
-    pub radius: Corners<Option<Rel<Length>>>,
+    pub radius: Corners<Option<Radius<Rel<Length>>>>,
+
+    /// Whether to round the square's corners without picking a radius. See
+    /// the [rectangle's documentation]($rect.rounded) for more details.
+    #[default(false)]
+    pub rounded: bool,
+
+    /// How much to smooth the rounded corners into a superellipse. See the
+    /// [rectangle's documentation]($rect.smoothing) for more details.
+    #[default(Scalar::ZERO)]
+    pub smoothing: Scalar,
 
     /// How much to pad the square's content. See the
-    /// [box's documentation]($box.inset) for more details.
+    /// [rectangle's documentation]($rect.inset) for more details.
     #[resolve]
     #[fold]
     #[default(Sides::splat(Abs::pt(5.0).into()))]
-    pub inset: Sides<Option<Rel<Length>>>,
+    pub inset: DirSides<Option<Rel<Length>>>,
+
+    /// How to align the square's content within its content box. See the
+    /// [rectangle's documentation]($rect.align) for more details.
+    pub align: Smart<Align>,
 
     /// How much to expand the square's size without affecting the layout. See
     /// the [box's documentation]($box.outset) for more details.
     #[resolve]
     #[fold]
-    pub outset: Sides<Option<Rel<Length>>>,
+    pub outset: DirSides<Option<Rel<Length>>>,
+
+    /// A drop shadow behind the square. See the
+    /// [rectangle's documentation]($rect.shadow) for more details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
+    /// How far the edge of the square's fill is blurred. See the
+    /// [rectangle's documentation]($rect.blur) for more details.
+    #[resolve]
+    pub blur: Length,
+
+    /// How opaque the square is. See the
+    /// [rectangle's documentation]($rect.opacity) for more details.
+    #[default(Ratio::one())]
+    pub opacity: Ratio,
+
+    /// Whether to clip the content inside the square to its outline. See the
+    /// [rectangle's documentation]($rect.clip) for more details.
+    #[default(false)]
+    pub clip: bool,
+
+    /// Whether a [link]($link) on the square only reacts within its
+    /// outline. See the [rectangle's documentation]($rect.link-shape) for more
+    /// details.
+    #[default(false)]
+    pub link_shape: bool,
+
+    /// How much to rotate the square clockwise around its center. See the
+    /// [rectangle's documentation]($rect.rotate) for more details.
+    pub rotate: Angle,
+
+    /// How much to rotate the square's content clockwise around the square's
+    /// center. See the [rectangle's documentation]($rect.content-rotate) for
+    /// more details.
+    pub content_rotate: Angle,
+
+    /// How much to skew the square horizontally around its center. See the
+    /// [rectangle's documentation]($rect.skew) for more details.
+    pub skew: Angle,
+
+    /// An amount to shift the square's baseline by when it is placed inline.
+    /// See the [rectangle's documentation]($rect.baseline) for more details.
+    #[resolve]
+    #[default(Smart::Custom(Rel::zero()))]
+    pub baseline: Smart<Rel<Length>>,
+
+    /// Whether to round the square's height up to a multiple of a baseline
+    /// grid. See the [rectangle's documentation]($rect.snap-height) for more
+    /// details.
+    #[resolve]
+    pub snap_height: Snap,
+
+    /// The size that the square takes on if it has no body. See the
+    /// [rectangle's documentation]($rect.default-size) for more details.
+    ///
+    /// The square's side length is the shorter of the two.
+    #[resolve]
+    #[default(Axes::new(Abs::pt(45.0).into(), Abs::pt(30.0).into()))]
+    pub default_size: Axes<Length>,
 
     /// The content to place into the square. The square expands to fit this
     /// content, keeping the 1-1 aspect ratio.
     ///
-    /// When this is omitted, the square takes on a default size of at most
-    /// `{30pt}`.
+    /// When this is omitted, the square takes on its
+    /// [default size]($square.default-size), which is `{30pt}` unless changed.
     #[positional]
     pub body: Option<Content>,
 }
@@ -245,20 +916,44 @@ impl Layout for SquareElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        layout(
-            engine,
-            styles,
-            regions,
-            ShapeKind::Square,
-            &self.body(styles),
-            Axes::new(self.width(styles), self.height(styles)),
-            self.fill(styles),
-            self.stroke(styles),
-            self.inset(styles),
-            self.outset(styles),
-            self.radius(styles),
-            self.span(),
-        )
+        let style = self.shape_style(styles);
+        layout(engine, styles, regions, &self.body(styles), style, self.span())
+    }
+}
+
+impl SquareElem {
+    /// The properties that determine the square's size and look.
+    fn shape_style(&self, styles: StyleChain) -> ShapeStyle {
+        ShapeStyle {
+            kind: self.kind(),
+            default: self.default_size(styles),
+            sizing: Axes::new(self.width(styles), self.height(styles)),
+            min: Axes::new(self.min_width(styles), self.min_height(styles)),
+            max: Axes::new(self.max_width(styles), self.max_height(styles)),
+            grow: Smart::Auto,
+            aspect: None,
+            breakable: false,
+            fill: self.fill(styles),
+            fill_hover: self.fill_hover(styles),
+            stroke: self.stroke(styles),
+            stroke_over: self.stroke_over(styles),
+            inset: self.inset(styles),
+            outset: self.outset(styles),
+            radius: rounded(self.radius(styles), self.rounded(styles)),
+            hole: None,
+            smoothing: self.smoothing(styles).get(),
+            shadow: self.shadow(styles),
+            blur: self.blur(styles),
+            opacity: self.opacity(styles),
+            clip: self.clip(styles),
+            link_shape: self.link_shape(styles),
+            align: self.align(styles),
+            rotate: self.rotate(styles),
+            content_rotate: self.content_rotate(styles),
+            skew: self.skew(styles),
+            baseline: self.baseline(styles),
+            snap_height: self.snap_height(styles),
+        }
     }
 }
 
@@ -276,44 +971,164 @@ impl Layout for SquareElem {
 ///   to fit the content.
 /// ]
 /// ```
-#[elem(Layout)]
+#[elem(Layout, BasicShape)]
 pub struct EllipseElem {
-    /// The ellipse's width, relative to its parent container.
-    pub width: Smart<Rel<Length>>,
+    /// The ellipse's width, relative to its parent container. See the
+    /// [rectangle's documentation]($rect.width) for fractional sizes.
+    pub width: Sizing,
 
     /// The ellipse's height, relative to its parent container.
-    pub height: Smart<Rel<Length>>,
+    pub height: Sizing,
+
+    /// The ellipse's minimum width. See the
+    /// [rectangle's documentation]($rect.min-width) for more details.
+    pub min_width: Option<Rel<Length>>,
+
+    /// The ellipse's maximum width.
+    pub max_width: Option<Rel<Length>>,
+
+    /// The ellipse's minimum height.
+    pub min_height: Option<Rel<Length>>,
+
+    /// The ellipse's maximum height.
+    pub max_height: Option<Rel<Length>>,
+
+    /// The ellipse's aspect ratio, that is, its width divided by its height.
+    /// See the [rectangle's documentation]($rect.aspect) for more details.
+    pub aspect: Option<Scalar>,
 
     /// How to fill the ellipse. See the [rectangle's documentation]($rect.fill)
     /// for more details.
-    pub fill: Option<Paint>,
+    #[default(Smart::Custom(None))]
+    pub fill: Smart<Option<Fill>>,
+
+    /// An alternative fill for when the pointer hovers over the ellipse. See
+    /// the [rectangle's documentation]($rect.fill-hover) for more details.
+    pub fill_hover: Option<Paint>,
 
     /// How to stroke the ellipse. See the
     /// [rectangle's documentation]($rect.stroke) for more details.
+    ///
+    /// When given a dictionary, each side strokes the quarter of the outline
+    /// centered on it.
+    ///
+    /// ```example
+    /// #ellipse(stroke: (top: 2pt + red, bottom: 2pt + blue))
+    /// ```
     #[resolve]
     #[fold]
-    pub stroke: Smart<Option<Stroke>>,
+    pub stroke: Smart<DirSides<Option<Option<Stroke>>>>,
+
+    /// Whether to draw the stroke on top of the ellipse's content instead of
+    /// behind it. See the [rectangle's documentation]($rect.stroke-over) for
+    /// more details.
+    #[default(false)]
+    pub stroke_over: bool,
 
     /// How much to pad the ellipse's content. See the
-    /// [box's documentation]($box.inset) for more details.
+    /// [rectangle's documentation]($rect.inset) for more details.
     #[resolve]
     #[fold]
     #[default(Sides::splat(Abs::pt(5.0).into()))]
-    pub inset: Sides<Option<Rel<Length>>>,
+    pub inset: DirSides<Option<Rel<Length>>>,
+
+    /// How to fit the ellipse's content into its curved outline.
+    ///
+    /// By default, the inset is increased such that the content fits into the
+    /// largest rectangle inscribed in the ellipse. For small content like a
+    /// short label, this padding can be excessive, so the `{"tight"}` mode
+    /// only applies the given inset.
+    ///
+    /// ```example
+    /// #set ellipse(stroke: 0.5pt)
+    /// #ellipse[Label]
+    /// #ellipse(inset-mode: "tight")[Label]
+    /// ```
+    pub inset_mode: InsetMode,
+
+    /// How to align the ellipse's content within its content box. See the
+    /// [rectangle's documentation]($rect.align) for more details.
+    pub align: Smart<Align>,
 
     /// How much to expand the ellipse's size without affecting the layout. See
     /// the [box's documentation]($box.outset) for more details.
     #[resolve]
     #[fold]
-    pub outset: Sides<Option<Rel<Length>>>,
+    pub outset: DirSides<Option<Rel<Length>>>,
 
-    /// The content to place into the ellipse.
-    ///
-    /// When this is omitted, the ellipse takes on a default size of at most
-    /// `{45pt}` by `{30pt}`.
-    #[positional]
-    pub body: Option<Content>,
-}
+    /// A drop shadow behind the ellipse. See the
+    /// [rectangle's documentation]($rect.shadow) for more details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
+    /// How far the edge of the ellipse's fill is blurred. See the
+    /// [rectangle's documentation]($rect.blur) for more details.
+    #[resolve]
+    pub blur: Length,
+
+    /// How opaque the ellipse is. See the
+    /// [rectangle's documentation]($rect.opacity) for more details.
+    #[default(Ratio::one())]
+    pub opacity: Ratio,
+
+    /// Whether to clip the content inside the ellipse to its outline.
+    ///
+    /// ```example
+    /// #ellipse(
+    ///   width: 60pt,
+    ///   height: 40pt,
+    ///   inset: 0pt,
+    ///   clip: true,
+    ///   image("tiger.jpg", width: 60pt),
+    /// )
+    /// ```
+    #[default(false)]
+    pub clip: bool,
+
+    /// Whether a [link]($link) on the ellipse only reacts within its
+    /// outline. See the [rectangle's documentation]($rect.link-shape) for more
+    /// details.
+    #[default(false)]
+    pub link_shape: bool,
+
+    /// How much to rotate the ellipse clockwise around its center. See the
+    /// [rectangle's documentation]($rect.rotate) for more details.
+    pub rotate: Angle,
+
+    /// How much to rotate the ellipse's content clockwise around the ellipse's
+    /// center. See the [rectangle's documentation]($rect.content-rotate) for
+    /// more details.
+    pub content_rotate: Angle,
+
+    /// How much to skew the ellipse horizontally around its center. See the
+    /// [rectangle's documentation]($rect.skew) for more details.
+    pub skew: Angle,
+
+    /// An amount to shift the ellipse's baseline by when it is placed inline.
+    /// See the [rectangle's documentation]($rect.baseline) for more details.
+    #[resolve]
+    #[default(Smart::Custom(Rel::zero()))]
+    pub baseline: Smart<Rel<Length>>,
+
+    /// Whether to round the ellipse's height up to a multiple of a baseline
+    /// grid. See the [rectangle's documentation]($rect.snap-height) for more
+    /// details.
+    #[resolve]
+    pub snap_height: Snap,
+
+    /// The size that the ellipse takes on if it has no body. See the
+    /// [rectangle's documentation]($rect.default-size) for more details.
+    #[resolve]
+    #[default(Axes::new(Abs::pt(45.0).into(), Abs::pt(30.0).into()))]
+    pub default_size: Axes<Length>,
+
+    /// The content to place into the ellipse.
+    ///
+    /// When this is omitted, the ellipse takes on its
+    /// [default size]($ellipse.default-size).
+    #[positional]
+    pub body: Option<Content>,
+}
 
 impl Layout for EllipseElem {
     #[tracing::instrument(name = "EllipseElem::layout", skip_all)]
@@ -323,20 +1138,44 @@ impl Layout for EllipseElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        layout(
-            engine,
-            styles,
-            regions,
-            ShapeKind::Ellipse,
-            &self.body(styles),
-            Axes::new(self.width(styles), self.height(styles)),
-            self.fill(styles),
-            self.stroke(styles).map(Sides::splat),
-            self.inset(styles),
-            self.outset(styles),
-            Corners::splat(Rel::zero()),
-            self.span(),
-        )
+        let style = self.shape_style(styles);
+        layout(engine, styles, regions, &self.body(styles), style, self.span())
+    }
+}
+
+impl EllipseElem {
+    /// The properties that determine the ellipse's size and look.
+    fn shape_style(&self, styles: StyleChain) -> ShapeStyle {
+        ShapeStyle {
+            kind: self.kind(),
+            default: self.default_size(styles),
+            sizing: Axes::new(self.width(styles), self.height(styles)),
+            min: Axes::new(self.min_width(styles), self.min_height(styles)),
+            max: Axes::new(self.max_width(styles), self.max_height(styles)),
+            grow: Smart::Auto,
+            aspect: self.aspect(styles),
+            breakable: false,
+            fill: self.fill(styles),
+            fill_hover: self.fill_hover(styles),
+            stroke: self.stroke(styles),
+            stroke_over: self.stroke_over(styles),
+            inset: self.inset_mode(styles).apply(self.inset(styles)),
+            outset: self.outset(styles),
+            radius: Corners::splat(Radius::default()),
+            hole: None,
+            smoothing: 0.0,
+            shadow: self.shadow(styles),
+            blur: self.blur(styles),
+            opacity: self.opacity(styles),
+            clip: self.clip(styles),
+            link_shape: self.link_shape(styles),
+            align: self.align(styles),
+            rotate: self.rotate(styles),
+            content_rotate: self.content_rotate(styles),
+            skew: self.skew(styles),
+            baseline: self.baseline(styles),
+            snap_height: self.snap_height(styles),
+        }
     }
 }
 
@@ -354,66 +1193,207 @@ impl Layout for EllipseElem {
 ///   sized to fit.
 /// ]
 /// ```
-#[elem(Layout)]
+#[elem(Layout, BasicShape)]
 pub struct CircleElem {
-    /// The circle's radius. This is mutually exclusive with `width` and
-    /// `height`.
+    /// The circle's radius. This is mutually exclusive with `diameter`,
+    /// `width`, and `height`.
     #[external]
     pub radius: Length,
 
-    /// The circle's width. This is mutually exclusive with `radius` and
-    /// `height`.
+    /// The circle's diameter, that is, twice its radius. This is mutually
+    /// exclusive with `radius`, `width`, and `height`.
+    ///
+    /// ```example
+    /// #circle(diameter: 30pt, fill: aqua)
+    /// ```
+    #[external]
+    pub diameter: Length,
+
+    /// The circle's width. This is mutually exclusive with `radius`,
+    /// `diameter`, and `height`.
     ///
-    /// In contrast to `radius`, this can be relative to the parent container's
-    /// width.
+    /// In contrast to `radius` and `diameter`, this can be relative to the
+    /// parent container's width or [fractional]($rect.width).
     #[parse(
-        let size = args
-            .named::<Smart<Length>>("radius")?
-            .map(|s| s.map(|r| 2.0 * Rel::from(r)));
+        let radius = args.named::<Spanned<Smart<Length>>>("radius")?;
+        let diameter = args.named::<Spanned<Smart<Length>>>("diameter")?;
+        let size = match (radius, diameter) {
+            (Some(_), Some(diameter)) => {
+                bail!(diameter.span, "cannot set both radius and diameter")
+            }
+            (Some(Spanned { v: radius, .. }), None) => {
+                Some(radius.map_or(Sizing::Auto, |r| Sizing::Rel(2.0 * Rel::from(r))))
+            }
+            (None, Some(Spanned { v: diameter, .. })) => {
+                Some(diameter.map_or(Sizing::Auto, |d| Sizing::Rel(d.into())))
+            }
+            (None, None) => None,
+        };
         match size {
             None => args.named("width")?,
             size => size,
         }
     )]
-    pub width: Smart<Rel<Length>>,
+    pub width: Sizing,
 
-    /// The circle's height. This is mutually exclusive with `radius` and
-    /// `width`.
+    /// The circle's height. This is mutually exclusive with `radius`,
+    /// `diameter`, and `width`.
     ///
-    /// In contrast to `radius`, this can be relative to the parent container's
-    /// height.
+    /// In contrast to `radius` and `diameter`, this can be relative to the
+    /// parent container's height.
     #[parse(match size {
         None => args.named("height")?,
         size => size,
     })]
-    pub height: Smart<Rel<Length>>,
+    pub height: Sizing,
+
+    /// The circle's minimum width. See the
+    /// [rectangle's documentation]($rect.min-width) for more details.
+    ///
+    /// The limits of both axes apply to the circle's common side length.
+    pub min_width: Option<Rel<Length>>,
+
+    /// The circle's maximum width.
+    pub max_width: Option<Rel<Length>>,
+
+    /// The circle's minimum height.
+    pub min_height: Option<Rel<Length>>,
+
+    /// The circle's maximum height.
+    pub max_height: Option<Rel<Length>>,
 
     /// How to fill the circle. See the [rectangle's documentation]($rect.fill)
     /// for more details.
-    pub fill: Option<Paint>,
+    #[default(Smart::Custom(None))]
+    pub fill: Smart<Option<Fill>>,
+
+    /// An alternative fill for when the pointer hovers over the circle. See
+    /// the [rectangle's documentation]($rect.fill-hover) for more details.
+    pub fill_hover: Option<Paint>,
 
     /// How to stroke the circle. See the
-    /// [rectangle's documentation]($rect.stroke) for more details.
+    /// [ellipse's documentation]($ellipse.stroke) for more details.
     #[resolve]
     #[fold]
     #[default(Smart::Auto)]
-    pub stroke: Smart<Option<Stroke>>,
+    pub stroke: Smart<DirSides<Option<Option<Stroke>>>>,
+
+    /// Whether to draw the stroke on top of the circle's content instead of
+    /// behind it. See the [rectangle's documentation]($rect.stroke-over) for
+    /// more details.
+    #[default(false)]
+    pub stroke_over: bool,
 
     /// How much to pad the circle's content. See the
-    /// [box's documentation]($box.inset) for more details.
+    /// [rectangle's documentation]($rect.inset) for more details.
     #[resolve]
     #[fold]
     #[default(Sides::splat(Abs::pt(5.0).into()))]
-    pub inset: Sides<Option<Rel<Length>>>,
+    pub inset: DirSides<Option<Rel<Length>>>,
+
+    /// How to fit the circle's content into its curved outline. See the
+    /// [ellipse's documentation]($ellipse.inset-mode) for more details.
+    pub inset_mode: InsetMode,
+
+    /// How to align the circle's content within its content box. See the
+    /// [rectangle's documentation]($rect.align) for more details.
+    pub align: Smart<Align>,
 
     /// How much to expand the circle's size without affecting the layout. See
     /// the [box's documentation]($box.outset) for more details.
     #[resolve]
     #[fold]
-    pub outset: Sides<Option<Rel<Length>>>,
+    pub outset: DirSides<Option<Rel<Length>>>,
+
+    /// A drop shadow behind the circle. See the
+    /// [rectangle's documentation]($rect.shadow) for more details.
+    #[resolve]
+    pub shadow: Option<Shadow>,
+
+    /// How far the edge of the circle's fill is blurred. See the
+    /// [rectangle's documentation]($rect.blur) for more details.
+    #[resolve]
+    pub blur: Length,
+
+    /// How opaque the circle is. See the
+    /// [rectangle's documentation]($rect.opacity) for more details.
+    #[default(Ratio::one())]
+    pub opacity: Ratio,
+
+    /// Whether to clip the content inside the circle to its outline. See the
+    /// [ellipse's documentation]($ellipse.clip) for more details.
+    #[default(false)]
+    pub clip: bool,
+
+    /// Whether a [link]($link) on the circle only reacts within its
+    /// outline. See the [rectangle's documentation]($rect.link-shape) for more
+    /// details.
+    #[default(false)]
+    pub link_shape: bool,
+
+    /// How much to rotate the circle clockwise around its center. See the
+    /// [rectangle's documentation]($rect.rotate) for more details.
+    pub rotate: Angle,
+
+    /// How much to rotate the circle's content clockwise around the circle's
+    /// center. See the [rectangle's documentation]($rect.content-rotate) for
+    /// more details.
+    pub content_rotate: Angle,
+
+    /// How much to skew the circle horizontally around its center. See the
+    /// [rectangle's documentation]($rect.skew) for more details.
+    pub skew: Angle,
+
+    /// An amount to shift the circle's baseline by when it is placed inline.
+    /// See the [rectangle's documentation]($rect.baseline) for more details.
+    #[resolve]
+    #[default(Smart::Custom(Rel::zero()))]
+    pub baseline: Smart<Rel<Length>>,
+
+    /// Whether to round the circle's height up to a multiple of a baseline
+    /// grid. See the [rectangle's documentation]($rect.snap-height) for more
+    /// details.
+    #[resolve]
+    pub snap_height: Snap,
+
+    /// The radius of a hole in the middle of the circle, which turns it into a
+    /// ring. A relative radius is relative to the circle's radius.
+    ///
+    /// The fill only covers the area between the outer and the inner edge,
+    /// while the stroke outlines both edges. The hole must be smaller than the
+    /// circle.
+    ///
+    /// ```example
+    /// #stack(
+    ///   dir: ltr,
+    ///   spacing: 1fr,
+    ///   circle(radius: 20pt, hole-radius: 12pt, fill: eastern),
+    ///   circle(radius: 20pt, hole-radius: 80%, fill: red, stroke: 1pt),
+    /// )
+    /// ```
+    #[resolve]
+    pub hole_radius: Option<Rel<Length>>,
+
+    /// The diameter that the circle takes on if it has no body.
+    ///
+    /// In contrast to the other shapes, a circle has no default width and
+    /// height to pick from, so its default size is a single diameter. A set
+    /// `radius`, `width`, or `height` takes precedence and the diameter is
+    /// limited to the available space.
+    ///
+    /// ```example
+    /// #set circle(default-diameter: 12pt)
+    /// #circle() #circle(radius: 12pt)
+    /// ```
+    #[resolve]
+    #[default(Abs::pt(30.0).into())]
+    pub default_diameter: Length,
 
     /// The content to place into the circle. The circle expands to fit this
     /// content, keeping the 1-1 aspect ratio.
+    ///
+    /// When this is omitted, the circle takes on its
+    /// [default diameter]($circle.default-diameter).
     #[positional]
     pub body: Option<Content>,
 }
@@ -426,65 +1406,545 @@ impl Layout for CircleElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        layout(
-            engine,
-            styles,
-            regions,
-            ShapeKind::Circle,
-            &self.body(styles),
-            Axes::new(self.width(styles), self.height(styles)),
-            self.fill(styles),
-            self.stroke(styles).map(Sides::splat),
-            self.inset(styles),
-            self.outset(styles),
-            Corners::splat(Rel::zero()),
-            self.span(),
-        )
+        let style = self.shape_style(styles);
+        layout(engine, styles, regions, &self.body(styles), style, self.span())
+    }
+}
+
+impl CircleElem {
+    /// The properties that determine the circle's size and look.
+    fn shape_style(&self, styles: StyleChain) -> ShapeStyle {
+        ShapeStyle {
+            kind: self.kind(),
+            default: Size::splat(self.default_diameter(styles)),
+            sizing: Axes::new(self.width(styles), self.height(styles)),
+            min: Axes::new(self.min_width(styles), self.min_height(styles)),
+            max: Axes::new(self.max_width(styles), self.max_height(styles)),
+            grow: Smart::Auto,
+            aspect: None,
+            breakable: false,
+            fill: self.fill(styles),
+            fill_hover: self.fill_hover(styles),
+            stroke: self.stroke(styles),
+            stroke_over: self.stroke_over(styles),
+            inset: self.inset_mode(styles).apply(self.inset(styles)),
+            outset: self.outset(styles),
+            radius: Corners::splat(Radius::default()),
+            hole: self.hole_radius(styles),
+            smoothing: 0.0,
+            shadow: self.shadow(styles),
+            blur: self.blur(styles),
+            opacity: self.opacity(styles),
+            clip: self.clip(styles),
+            link_shape: self.link_shape(styles),
+            align: self.align(styles),
+            rotate: self.rotate(styles),
+            content_rotate: self.content_rotate(styles),
+            skew: self.skew(styles),
+            baseline: self.baseline(styles),
+            snap_height: self.snap_height(styles),
+        }
     }
 }
 
+/// The properties of a shape element that determine its size and look.
+struct ShapeStyle {
+    kind: ShapeKind,
+    default: Size,
+    sizing: Axes<Sizing>,
+    min: Axes<Option<Rel<Length>>>,
+    max: Axes<Option<Rel<Length>>>,
+    grow: Smart<bool>,
+    aspect: Option<Scalar>,
+    breakable: bool,
+    fill: Smart<Option<Fill>>,
+    fill_hover: Option<Paint>,
+    stroke: Smart<Sides<Option<Stroke<Abs>>>>,
+    stroke_over: bool,
+    inset: Sides<Rel<Abs>>,
+    outset: Sides<Rel<Abs>>,
+    radius: Corners<Radius<Rel<Abs>>>,
+    hole: Option<Rel<Abs>>,
+    smoothing: f64,
+    shadow: Option<Shadow<Abs>>,
+    blur: Abs,
+    opacity: Ratio,
+    clip: bool,
+    link_shape: bool,
+    align: Smart<Align>,
+    rotate: Angle,
+    content_rotate: Angle,
+    skew: Angle,
+    baseline: Smart<Rel<Abs>>,
+    snap_height: Snap<Abs>,
+}
+
 /// Layout a shape.
 #[tracing::instrument(name = "shape::layout", skip_all)]
-#[allow(clippy::too_many_arguments)]
 fn layout(
     engine: &mut Engine,
     styles: StyleChain,
     regions: Regions,
-    kind: ShapeKind,
     body: &Option<Content>,
-    sizing: Axes<Smart<Rel<Length>>>,
-    fill: Option<Paint>,
-    stroke: Smart<Sides<Option<Stroke<Abs>>>>,
-    mut inset: Sides<Rel<Abs>>,
-    outset: Sides<Rel<Abs>>,
-    radius: Corners<Rel<Abs>>,
+    style: ShapeStyle,
     span: Span,
 ) -> SourceResult<Fragment> {
+    let mut frames = layout_frames(engine, styles, regions, body, &style, span)?;
+    let ShapeStyle {
+        kind,
+        fill,
+        fill_hover,
+        stroke,
+        stroke_over,
+        outset,
+        radius,
+        hole,
+        smoothing,
+        shadow,
+        blur,
+        opacity,
+        clip,
+        link_shape,
+        rotate,
+        content_rotate,
+        skew,
+        baseline,
+        ..
+    } = style;
+
+    if !(0.0..=1.0).contains(&smoothing) {
+        bail!(span, "smoothing must be between 0 and 1");
+    }
+
+    if skew.to_deg().abs() >= 90.0 {
+        bail!(span, "skew must be between -90deg and 90deg");
+    }
+
+    // Resolve relative stroke thicknesses against the shorter side of the
+    // whole shape, even if it is broken across regions.
+    let whole = frames.iter().fold(Size::zero(), |whole, frame| {
+        Size::new(whole.x.max(frame.width()), whole.y + frame.height())
+    });
+    let stroke = stroke.map(|strokes| {
+        strokes.map(|stroke| stroke.map(|stroke| stroke.relative_to(whole.min_by_side())))
+    });
+
+    // Move the stroke's outline such that each stroke is placed inside of,
+    // centered on, or outside of the shape's boundary. The fill, clip, and
+    // links stay at the boundary.
+    let stroke_outset = aligned_outset(outset, &stroke);
+
+    // An automatic fill follows the text color. A paint of the theme is
+    // looked up right away, but a fill function is only called once the size
+    // of each fragment is known.
+    let fill = fill.unwrap_or_else(|| Some(TextElem::fill_in(styles).into()));
+    let fill = fill.map(|fill| fill.resolve_token(styles, span)).transpose()?;
+    let has_fill = fill.is_some();
+    let (func, fill) = match fill {
+        Some(Fill::Func(func)) => (Some(func), None),
+        fill => (None, fill),
+    };
+
+    // Prepare stroke. An explicit `none` arrives as custom strokes that are
+    // `None` on all sides, so it disables the stroke regardless of the fill.
+    let stroke = match stroke {
+        Smart::Auto if !has_fill => Sides::splat(Some(FixedStroke::default())),
+        Smart::Auto => Sides::splat(None),
+        Smart::Custom(strokes) => strokes.map(|s| s.map(Stroke::unwrap_or_default)),
+    };
+
+    // Apply the opacity.
+    let stroke = stroke.map(|stroke| {
+        stroke.map(|mut stroke| {
+            stroke.paint = stroke.paint.with_opacity(opacity);
+            stroke
+        })
+    });
+    let shadow = shadow.map(|mut shadow| {
+        shadow.color = shadow.color.scale_alpha(opacity.get().clamp(0.0, 1.0) as f32);
+        shadow
+    });
+
+    let prepared = prepare_fill(fill.clone(), opacity, blur);
+    warn_opaque(engine, fill.as_ref(), &stroke, opacity, span);
+
+    // Links that should only react within the shape's outline.
+    let links: Vec<_> = MetaElem::data_in(styles)
+        .into_iter()
+        .filter_map(|meta| match meta {
+            Meta::Link(dest) if link_shape => Some(dest),
+            _ => None,
+        })
+        .collect();
+
+    // Decorate each fragment of the shape. Everything that depends on a
+    // fragment's size, like relative radii or a gradient fill, is resolved
+    // only here, once squares and circles were laid out a second time. If
+    // the shape is broken across regions, only its outermost corners are
+    // rounded. An empty first fragment is not decorated if the content moved
+    // to the next region.
+    let count = frames.len();
+    let skip = count > 1
+        && frames[0].is_empty()
+        && frames[1..].iter().any(|frame| !frame.is_empty());
+    for (i, frame) in frames.iter_mut().enumerate().skip(skip as usize) {
+        let radius = fragment_radius(radius, i, count);
+
+        // Rotate the content within the fragment before it is clipped and
+        // decorated, so that the shape itself stays in place.
+        if !content_rotate.is_zero() {
+            let center = (frame.size() / 2.0).to_point();
+            frame.transform(
+                Transform::translate(center.x, center.y)
+                    .pre_concat(Transform::rotate(content_rotate))
+                    .pre_concat(Transform::translate(-center.x, -center.y)),
+            );
+        }
+
+        // The radius of a ring's hole, which must leave a part of the circle.
+        let hole = match hole {
+            Some(hole) => {
+                let outer = frame.size().min_by_side() / 2.0;
+                let hole = hole.relative_to(outer);
+                if hole >= outer {
+                    bail!(span, "hole radius must be smaller than the circle's radius");
+                }
+                Some(hole).filter(|hole| *hole > Abs::zero())
+            }
+            None => None,
+        };
+
+        // Call a fill function with the size of the fragment.
+        let (fill, image, blurred, overlays) = match &func {
+            Some(func) => {
+                let Size { x, y } = frame.size();
+                let fill = func
+                    .call(engine, [dict! { "width" => x, "height" => y }])?
+                    .cast::<Option<Fill>>()
+                    .at(span)?
+                    .map(|fill| fill.resolve_token(styles, span))
+                    .transpose()?;
+                if matches!(fill, Some(Fill::Func(_))) {
+                    bail!(span, "fill function must return a paint, an image, or none");
+                }
+                warn_opaque(engine, fill.as_ref(), &stroke, opacity, span);
+                prepare_fill(fill, opacity, blur)
+            }
+            None => prepared.clone(),
+        };
+
+        // The area covered by the shape, including the outset, and the area
+        // enclosed by the center of the stroke.
+        let (pos, size) = outset_area(frame.size(), outset);
+        let (stroke_pos, stroke_size) = outset_area(frame.size(), stroke_outset);
+        let stroked = stroke_size.x > Abs::zero() && stroke_size.y > Abs::zero();
+
+        // Clip the contents. Links in the contents are clipped, too, so that
+        // they only react within the visible area.
+        if clip {
+            let mut path = if kind.is_round() {
+                clip_ellipse(size, &stroke)
+            } else {
+                smooth_corners(clip_rect(size, radius, &stroke), smoothing)
+            };
+            let outline = Geometry::Path(path.clone()).filled(Color::BLACK.into());
+            let strips: Vec<_> = link_strips(&outline, size)
+                .into_iter()
+                .map(|(offset, strip)| (pos + offset, strip))
+                .collect();
+            clip_links(frame, &strips);
+            path.translate(pos);
+            frame.clip(path);
+        }
+
+        // A shape without area would only paint a stray hairline or dot.
+        if size.x <= Abs::zero() || size.y <= Abs::zero() {
+            continue;
+        }
+
+        // Add fill and/or stroke. A blurred fill is added separately below.
+        let sharp = fill.clone().filter(|_| blurred.is_none());
+        let styled = |size: Size, fill, stroke| -> Vec<Shape> {
+            if let Some(hole) = hole {
+                styled_ring(size, hole, fill, stroke)
+            } else if kind.is_round() {
+                styled_ellipse(size, fill, stroke)
+            } else {
+                styled_rect(size, radius, fill, stroke)
+                    .into_iter()
+                    .map(|shape| smooth_shape(shape, smoothing))
+                    .collect()
+            }
+        };
+
+        // The stroke goes either behind or on top of the content.
+        let none = Sides::splat(None);
+        let (behind, over) = if stroke_over {
+            (none.clone(), stroke.clone())
+        } else {
+            (stroke.clone(), none.clone())
+        };
+
+        // Overlaid paints go between the fill and the stroke. A stroke that
+        // is not centered on the boundary is drawn on its own outline.
+        if !overlays.is_empty() || (stroke_pos, stroke_size) != (pos, size) {
+            let mut shapes = styled(size, sharp, none.clone());
+            for paint in &overlays {
+                shapes.extend(styled(size, Some(paint.clone()), none.clone()));
+            }
+            shapes.retain(|shape| shape.fill.is_some());
+            let mut items = outline_items(shapes, pos, size, span);
+            if stroked && behind.iter().any(Option::is_some) {
+                let shapes = styled(stroke_size, None, behind);
+                items.extend(outline_items(shapes, stroke_pos, stroke_size, span));
+            }
+            frame.prepend_multiple(items);
+        } else if sharp.is_some() || behind.iter().any(Option::is_some) {
+            let shapes = styled(size, sharp, behind);
+            frame.prepend_multiple(outline_items(shapes, pos, size, span));
+        }
+
+        if stroked && over.iter().any(Option::is_some) {
+            let shapes = styled(stroke_size, None, over);
+            for (pos, item) in outline_items(shapes, stroke_pos, stroke_size, span) {
+                frame.push(pos, item);
+            }
+        }
+
+        // Add the blurred fill below the stroke.
+        if let Some(layers) = &blurred {
+            frame.prepend_multiple(soft_items(
+                kind,
+                (pos, size),
+                radius,
+                hole,
+                smoothing,
+                layers,
+                span,
+            ));
+        }
+
+        // Add the image fill below the stroke, clipped to the shape's outline.
+        if let Some(image) = &image {
+            let pod = Regions::one(size, Axes::splat(true));
+            let mut fill = image.layout(engine, styles, pod)?.into_frame();
+            fill.clip(if let Some(hole) = hole {
+                ring_path(size, hole)
+            } else if kind.is_round() {
+                ellipse_path(size)
+            } else {
+                smooth_corners(clip_rect(size, radius, &Sides::splat(None)), smoothing)
+            });
+            frame.prepend_frame(pos, fill);
+        }
+
+        // Add the shadow below everything else.
+        if let Some(shadow) = &shadow {
+            let pos = pos + Point::new(shadow.dx, shadow.dy);
+            let layers: Vec<_> = shadow
+                .layers()
+                .into_iter()
+                .map(|(grow, color)| (grow, color.into()))
+                .collect();
+            frame.prepend_multiple(soft_items(
+                kind,
+                (pos, size),
+                radius,
+                hole,
+                smoothing,
+                &layers,
+                span,
+            ));
+        }
+
+        // Store the alternative fill for interactive exports.
+        if let Some(paint) = &fill_hover {
+            let meta = Meta::HoverFill(paint.clone().with_opacity(opacity));
+            frame.push(pos, FrameItem::Meta(meta, size));
+        }
+
+        // Link the area within the outline.
+        if !links.is_empty() {
+            let geometry = if kind.is_round() {
+                Geometry::Path(ellipse_path(size))
+            } else if radius.iter().all(Radius::is_zero) {
+                Geometry::Rect(size)
+            } else {
+                let path = clip_rect(size, radius, &Sides::splat(None));
+                Geometry::Path(smooth_corners(path, smoothing))
+            };
+            let outline = Shape {
+                geometry,
+                fill: None,
+                stroke: None,
+                fill_rule: FillRule::default(),
+            };
+            for (offset, strip) in link_strips(&outline, size) {
+                for dest in &links {
+                    let item = FrameItem::Meta(Meta::Link(dest.clone()), strip);
+                    frame.push(pos + offset, item);
+                }
+            }
+        }
+    }
+
+    // Skew and rotate the whole shape, including the already rotated content,
+    // and grow its frame to the transformed bounding box. Then, shift the
+    // baseline and apply metadata.
+    let x_height = baseline.is_auto().then(|| x_height(engine, styles));
+    let transform =
+        Transform::rotate(rotate).pre_concat(Transform::skew(skew, Angle::zero()));
+    let frames = frames.into_iter().map(|mut frame| {
+        if !rotate.is_zero() || !skew.is_zero() {
+            frame.transform_bounded(transform);
+        }
+        let height = frame.height();
+        match (baseline, x_height) {
+            (Smart::Custom(shift), _) if !shift.is_zero() => {
+                frame.set_baseline(height - shift.relative_to(height));
+            }
+            (Smart::Auto, Some(x_height)) => {
+                frame.set_baseline((height + x_height) / 2.0);
+            }
+            _ => {}
+        }
+        if !frame.is_empty() {
+            frame.meta_iter(
+                MetaElem::data_in(styles)
+                    .into_iter()
+                    .filter(|meta| !(link_shape && matches!(meta, Meta::Link(_)))),
+            );
+        }
+        frame
+    });
+
+    Ok(Fragment::frames(frames.collect()))
+}
+
+/// Lay out the fragments of a shape with their final sizes, but without
+/// filling, stroking, or otherwise decorating them.
+fn layout_frames(
+    engine: &mut Engine,
+    styles: StyleChain,
+    regions: Regions,
+    body: &Option<Content>,
+    style: &ShapeStyle,
+    span: Span,
+) -> SourceResult<Vec<Frame>> {
+    let &ShapeStyle {
+        kind,
+        default,
+        sizing,
+        min,
+        max,
+        grow,
+        aspect,
+        breakable,
+        inset,
+        align,
+        snap_height,
+        ..
+    } = style;
+
+    // Align the content within the content box. This positions it along both
+    // axes because the content is laid out into an expanded region.
+    let body = match align {
+        Smart::Custom(align) => body.clone().map(|body| body.aligned(align)),
+        Smart::Auto => body.clone(),
+    };
+    let body = &body;
+
+    // A fractional size takes up the remaining space of the region, unless
+    // the region is unbounded along that axis. So does a shape without a size
+    // that grows.
+    let grow = grow.map_or(regions.expand, Axes::splat);
+    let sizing =
+        sizing.zip(grow).zip_map(
+            regions.size,
+            |(sizing, grow), available| match sizing {
+                Sizing::Rel(rel) => Smart::Custom(rel),
+                Sizing::Auto if !grow => Smart::Auto,
+                Sizing::Auto | Sizing::Fr(_) if available.is_finite() => {
+                    Smart::Custom(available.into())
+                }
+                Sizing::Auto | Sizing::Fr(_) => Smart::Auto,
+            },
+        );
+
+    let (min, max) = resolve_limits(styles, regions, kind, min, max, span)?;
+    let limit = |size: Size| size.max(min).min(max);
+    let unit = match snap_height {
+        Snap::None => None,
+        Snap::Lines => Some(line_pitch(engine, styles)),
+        Snap::Unit(unit) => Some(unit),
+    };
+    let snap = |size: Size| {
+        let height = unit.map_or(size.y, |unit| snap_to(size.y, unit));
+        if kind.is_quadratic() {
+            Size::splat(height)
+        } else {
+            Size::new(size.x, height)
+        }
+    };
     let resolved = sizing
-        .zip_map(regions.base(), |s, r| s.map(|v| v.resolve(styles).relative_to(r)));
+        .zip_map(regions.base(), |s, r| s.map(|v| v.resolve(styles).relative_to(r)))
+        .zip(min.zip(max))
+        .map(|(s, (min, max))| s.map(|v| v.max(min).min(max)));
 
-    let mut frame;
-    if let Some(child) = body {
-        let region = resolved.unwrap_or(regions.base());
-        if kind.is_round() {
-            inset = inset.map(|side| side + Ratio::new(0.5 - SQRT_2 / 4.0));
+    // The aspect ratio only matters if at most one dimension is fixed.
+    let aspect = aspect.map(Scalar::get);
+    if aspect.is_some_and(|aspect| !(aspect.is_finite() && aspect > 0.0)) {
+        bail!(span, "aspect ratio must be positive");
+    }
+    let aspect = aspect.filter(|_| resolved.x.is_auto() || resolved.y.is_auto());
+
+    let mut frames = vec![];
+    if let Some(child) = body.as_ref().filter(|_| breakable && aspect.is_none()) {
+        // Lay out the child across multiple regions.
+        let child = child.clone().padded(inset.map(|side| side.map(Length::from)));
+        let mut pod = regions;
+        pod.size.x = pod.size.x.min(max.x);
+        frames = layout_breakable(engine, styles, pod, &child, sizing)?;
+        for frame in &mut frames {
+            frame.size_mut().x = frame.width().max(min.x).min(max.x);
+            frame.set_size(snap(frame.size()));
         }
+    } else if let Some(child) = body {
+        let region = resolved.unwrap_or(regions.base().min(max));
 
-        // Pad the child.
+        // Pad the child. The padding resolves relative insets against the size
+        // of the padded frame, that is, the shape's own size.
         let child = child.clone().padded(inset.map(|side| side.map(Length::from)));
         let expand = sizing.as_ref().map(Smart::is_custom);
-        let pod = Regions::one(region, expand);
-        frame = child.layout(engine, styles, pod)?.into_frame();
+
+        // If a square or circle has a fixed width and height, we already know
+        // its side length and can directly lay out the child into the square
+        // region instead of measuring it first.
+        let fixed = kind.is_quadratic() && expand.x && expand.y;
+        let pod = if fixed {
+            Regions::one(Size::splat(region.min_by_side()), Axes::splat(true))
+        } else {
+            Regions::one(region, expand)
+        };
+        let mut frame = child.layout(engine, styles, pod)?.into_frame();
 
         // Enforce correct size.
         *frame.size_mut() = expand.select(region, frame.size());
 
         // Relayout with full expansion into square region to make sure
         // the result is really a square or circle.
-        if kind.is_quadratic() {
-            frame.set_size(Size::splat(frame.size().max_by_side()));
-            let length = frame.size().max_by_side().min(region.min_by_side());
-            let pod = Regions::one(Size::splat(length), Axes::splat(true));
+        if kind.is_quadratic() && !fixed {
+            if let Some(length) = square_relayout(frame.size(), region, min, max) {
+                let pod = Regions::one(Size::splat(length), Axes::splat(true));
+                frame = child.layout(engine, styles, pod)?.into_frame();
+            }
+        } else if let Some(aspect) = aspect {
+            // Relayout with full expansion into a region with the requested
+            // aspect ratio.
+            let target = grow_to_aspect(frame.size(), aspect);
+            let bound = shrink_to_aspect(region, aspect);
+            let size = if target.x <= bound.x { target } else { bound };
+            let pod = Regions::one(size, Axes::splat(true));
             frame = child.layout(engine, styles, pod)?.into_frame();
         }
 
@@ -493,41 +1953,542 @@ fn layout(
         if kind.is_quadratic() {
             frame.set_size(Size::splat(frame.size().max_by_side()));
         }
+
+        // Keep the size within the limits. If this changes the size, relative
+        // insets would no longer match it, so the child is laid out again.
+        let limited = limit(frame.size());
+        let relative = inset.iter().any(|side| !side.rel.is_zero());
+        if limited != frame.size() && relative {
+            let pod = Regions::one(limited, Axes::splat(true));
+            frame = child.layout(engine, styles, pod)?.into_frame();
+        }
+
+        // Round the height up to the grid. The child is laid out again so
+        // that it is aligned within the taller shape.
+        let snapped = snap(limited);
+        if snapped != limited {
+            let pod = Regions::one(snapped, Axes::splat(true));
+            frame = child.layout(engine, styles, pod)?.into_frame();
+        }
+        frame.set_size(snapped);
+        frames.push(frame);
     } else {
-        // The default size that a shape takes on if it has no child and
-        // enough space.
-        let default = Size::new(Abs::pt(45.0), Abs::pt(30.0));
+        // Take on the default size if there is enough space. Each axis
+        // falls back to the default on its own, so a size along just one
+        // axis leaves the other one at the default.
         let mut size = resolved.unwrap_or(default.min(regions.base()));
         if kind.is_quadratic() {
-            size = Size::splat(size.min_by_side());
+            // A single given side determines the side length instead of the
+            // default of the other side. It is still limited by the space
+            // along the other axis.
+            let base = regions.base();
+            size = match (resolved.x, resolved.y) {
+                (Smart::Custom(width), Smart::Auto) => Size::splat(width.min(base.y)),
+                (Smart::Auto, Smart::Custom(height)) => Size::splat(height.min(base.x)),
+                _ => Size::splat(size.min_by_side()),
+            };
+        } else if let Some(aspect) = aspect {
+            size = match (resolved.x, resolved.y) {
+                (Smart::Custom(width), _) => Size::new(width, width / aspect),
+                (_, Smart::Custom(height)) => Size::new(height * aspect, height),
+                (Smart::Auto, Smart::Auto) => shrink_to_aspect(size, aspect),
+            };
         }
-        frame = Frame::soft(size);
+        frames.push(Frame::soft(snap(limit(size))));
     }
 
-    // Prepare stroke.
-    let stroke = match stroke {
-        Smart::Auto if fill.is_none() => Sides::splat(Some(FixedStroke::default())),
-        Smart::Auto => Sides::splat(None),
-        Smart::Custom(strokes) => strokes.map(|s| s.map(Stroke::unwrap_or_default)),
+    Ok(frames)
+}
+
+/// Lay out the fragments of a shape like [`layout_frames`], but without
+/// affecting the locations of the actual layout, like `Content::measure`.
+fn measure_frames(
+    engine: &mut Engine,
+    styles: StyleChain,
+    regions: Regions,
+    body: &Option<Content>,
+    style: &ShapeStyle,
+    span: Span,
+) -> SourceResult<Vec<Frame>> {
+    let mut locator = Locator::chained(engine.locator.track());
+    let mut engine = Engine {
+        world: engine.world,
+        route: engine.route.clone(),
+        introspector: engine.introspector,
+        locator: &mut locator,
+        tracer: TrackedMut::reborrow_mut(&mut engine.tracer),
+    };
+    layout_frames(&mut engine, styles, regions, body, style, span)
+}
+
+/// Determine the size of a shape with an automatic width and height.
+fn natural_size(
+    engine: &mut Engine,
+    styles: StyleChain,
+    base: Size,
+    body: &Option<Content>,
+    style: ShapeStyle,
+    span: Span,
+) -> SourceResult<Size> {
+    let style = ShapeStyle {
+        sizing: Axes::splat(Sizing::Auto),
+        breakable: false,
+        ..style
+    };
+    let pod = Regions::one(base, Axes::splat(false));
+    let frames = measure_frames(engine, styles, pod, body, &style, span)?;
+    Ok(frames.first().map_or(Size::zero(), Frame::size))
+}
+
+/// Give the sharp corners of a shape with `rounded: true` a radius of an
+/// eighth of its shorter side.
+fn rounded(
+    radius: Corners<Radius<Rel<Abs>>>,
+    rounded: bool,
+) -> Corners<Radius<Rel<Abs>>> {
+    if !rounded {
+        return radius;
+    }
+
+    // A circular radius is relative to the shorter side.
+    let default = Radius::circular(Ratio::new(0.125).into());
+    radius.map(|radius| if radius.is_zero() { default } else { radius })
+}
+
+/// The corner radii of the `i`-th of `count` fragments of a broken shape. Only
+/// the top corners of the first and the bottom corners of the last fragment
+/// are rounded.
+fn fragment_radius<T: Default>(
+    mut radius: Corners<T>,
+    i: usize,
+    count: usize,
+) -> Corners<T> {
+    if i > 0 {
+        radius.top_left = T::default();
+        radius.top_right = T::default();
+    }
+    if i + 1 < count {
+        radius.bottom_left = T::default();
+        radius.bottom_right = T::default();
+    }
+    radius
+}
+
+/// The maximum number of strips that approximate the area of a link on a
+/// shape.
+const LINK_STRIPS: usize = 16;
+
+/// The minimum height of such a strip, in points. Smaller shapes use fewer
+/// strips.
+const LINK_STRIP_HEIGHT: f64 = 4.0;
+
+/// Restrict the links in a frame to the given strips, which approximate the
+/// area a shape is clipped to.
+///
+/// Links in groups with a transform are left as they are.
+fn clip_links(frame: &mut Frame, strips: &[(Point, Size)]) {
+    let has_links = frame.items().any(|(_, item)| {
+        matches!(item, FrameItem::Meta(Meta::Link(_), _) | FrameItem::Group(_))
+    });
+    if !has_links {
+        return;
+    }
+
+    let items: Vec<_> = frame.items().cloned().collect();
+    frame.clear();
+    for (pos, item) in items {
+        match item {
+            FrameItem::Meta(Meta::Link(dest), size) => {
+                for &(offset, strip) in strips {
+                    let min = pos.max(offset);
+                    let max = (pos + size.to_point()).min(offset + strip.to_point());
+                    if max.x > min.x && max.y > min.y {
+                        let item = FrameItem::Meta(
+                            Meta::Link(dest.clone()),
+                            (max - min).to_size(),
+                        );
+                        frame.push(min, item);
+                    }
+                }
+            }
+            FrameItem::Group(mut group) if group.transform.is_identity() => {
+                let strips: Vec<_> =
+                    strips.iter().map(|&(offset, strip)| (offset - pos, strip)).collect();
+                clip_links(&mut group.frame, &strips);
+                frame.push(pos, FrameItem::Group(group));
+            }
+            item => frame.push(pos, item),
+        }
+    }
+}
+
+/// Approximate the area within a shape's outline with horizontal strips that
+/// fit into it. The outline must be convex, which those of all basic shapes
+/// are. Adjacent strips with the same horizontal extent are merged.
+fn link_strips(outline: &Shape, size: Size) -> Vec<(Point, Size)> {
+    if let Geometry::Rect(size) = outline.geometry {
+        return vec![(Point::zero(), size)];
+    }
+
+    // The horizontal extent of the outline at a height, found by bisection
+    // from the middle towards both sides.
+    let extent = |y: Abs| {
+        let bisect = |mut inside: Abs, mut outside: Abs| {
+            for _ in 0..24 {
+                let mid = (inside + outside) / 2.0;
+                if outline.contains(Point::new(mid, y)) {
+                    inside = mid;
+                } else {
+                    outside = mid;
+                }
+            }
+            inside
+        };
+        let middle = size.x / 2.0;
+        (bisect(middle, Abs::zero()), bisect(middle, size.x))
     };
 
-    // Add fill and/or stroke.
-    if fill.is_some() || stroke.iter().any(Option::is_some) {
-        if kind.is_round() {
-            let outset = outset.relative_to(frame.size());
-            let size = frame.size() + outset.sum_by_axis();
-            let pos = Point::new(-outset.left, -outset.top);
-            let shape = ellipse(size, fill, stroke.left);
-            frame.prepend(pos, FrameItem::Shape(shape, span));
+    // Because the outline is convex, a strip fits into it if its top and
+    // bottom edges do.
+    let count = ((size.y.to_pt() / LINK_STRIP_HEIGHT) as usize).clamp(1, LINK_STRIPS);
+    let height = size.y / count as f64;
+    let mut strips: Vec<(Point, Size)> = vec![];
+    for i in 0..count {
+        let top = height * i as f64;
+        let (left_top, right_top) = extent(top);
+        let (left_bottom, right_bottom) = extent(top + height);
+        let left = left_top.max(left_bottom);
+        let right = right_top.min(right_bottom);
+        if right <= left {
+            continue;
+        }
+
+        match strips.last_mut() {
+            Some((pos, size))
+                if (pos.y + size.y).approx_eq(top)
+                    && pos.x.approx_eq(left)
+                    && (pos.x + size.x).approx_eq(right) =>
+            {
+                size.y += height;
+            }
+            _ => strips.push((Point::new(left, top), Size::new(right - left, height))),
+        }
+    }
+    strips
+}
+
+/// The x-height of the first available font, used to center shapes on it.
+fn x_height(engine: &Engine, styles: StyleChain) -> Abs {
+    let world = engine.world;
+    let variant = variant(styles);
+    families(styles)
+        .find_map(|family| world.font(world.book().select(family, variant)?))
+        .map_or(Abs::zero(), |font| font.metrics().x_height.at(TextElem::size_in(styles)))
+}
+
+/// The distance between the baselines of two lines of a paragraph, that is,
+/// the height of a line from its top to its bottom edge plus the leading.
+fn line_pitch(engine: &Engine, styles: StyleChain) -> Abs {
+    let world = engine.world;
+    let variant = variant(styles);
+    let size = TextElem::size_in(styles);
+    let height = families(styles)
+        .find_map(|family| world.font(world.book().select(family, variant)?))
+        .map_or(size, |font| {
+            let top = TextElem::top_edge_in(styles).resolve(size, &font, None);
+            let bottom = TextElem::bottom_edge_in(styles).resolve(size, &font, None);
+            top - bottom
+        });
+    height + ParElem::leading_in(styles)
+}
+
+/// Round `height` up to the next multiple of `unit`.
+fn snap_to(height: Abs, unit: Abs) -> Abs {
+    if unit <= Abs::zero() || !height.is_finite() {
+        return height;
+    }
+
+    // Tolerate rounding errors so that a height that already is on the grid
+    // stays the same.
+    let lines = (height / unit - 1e-6).ceil().max(0.0);
+    unit * lines
+}
+
+/// A fill that is ready to be painted: a paint, possibly split into blurred
+/// layers, or an image, as well as the paints overlaid on top of it.
+type PreparedFill =
+    (Option<Paint>, Option<ImageElem>, Option<Vec<(Abs, Paint)>>, Vec<Paint>);
+
+/// Separate an image fill from a paint fill, apply the opacity to the paints,
+/// and split the bottom paint into layers if it is blurred.
+fn prepare_fill(fill: Option<Fill>, opacity: Ratio, blur: Abs) -> PreparedFill {
+    let (fill, image, overlays) = match fill {
+        Some(Fill::Paint(paint)) => (Some(paint.with_opacity(opacity)), None, vec![]),
+        Some(Fill::Image(image)) => (None, Some(image), vec![]),
+        Some(Fill::Layers(paints)) => {
+            let mut paints = paints.into_iter().map(|paint| paint.with_opacity(opacity));
+            (paints.next(), None, paints.collect())
+        }
+        Some(Fill::Func(_) | Fill::Token(_)) | None => (None, None, vec![]),
+    };
+
+    // Patterns cannot be made translucent, so they are never blurred.
+    let blurred = fill
+        .as_ref()
+        .filter(|fill| blur > Abs::zero() && !matches!(fill, Paint::Pattern(_)))
+        .map(|fill| {
+            let alpha = match fill {
+                Paint::Solid(color) => color.alpha().unwrap_or(1.0),
+                _ => 1.0,
+            };
+            blur_layers(blur, alpha)
+                .into_iter()
+                .map(|(grow, layer)| {
+                    let factor = Ratio::new((layer / alpha) as f64);
+                    (grow, fill.clone().with_opacity(factor))
+                })
+                .collect()
+        });
+
+    (fill, image, blurred, overlays)
+}
+
+/// Warn if a translucent shape has a fill or stroke that cannot be made
+/// translucent.
+fn warn_opaque(
+    engine: &mut Engine,
+    fill: Option<&Fill>,
+    stroke: &Sides<Option<FixedStroke>>,
+    opacity: Ratio,
+    span: Span,
+) {
+    if opacity.get() >= 1.0 {
+        return;
+    }
+
+    // Whether any paint of the fill or stroke matches.
+    let any = |check: fn(&Paint) -> bool| {
+        let filled = match fill {
+            Some(Fill::Paint(paint)) => check(paint),
+            Some(Fill::Layers(paints)) => paints.iter().any(check),
+            Some(Fill::Image(_) | Fill::Func(_) | Fill::Token(_)) | None => false,
+        };
+        filled || stroke.iter().flatten().any(|stroke| check(&stroke.paint))
+    };
+
+    let image = matches!(fill, Some(Fill::Image(_)));
+    if image || any(|paint| matches!(paint, Paint::Pattern(_))) {
+        engine.tracer.warn(warning!(
+            span, "opacity is not applied to patterns and images";
+            hint: "patterns and images are painted fully opaque"
+        ));
+    }
+
+    // CMYK colors have no alpha channel, so they can only be made translucent
+    // in RGB.
+    if any(|paint| matches!(paint, Paint::Solid(Color::Cmyk(_)))) {
+        engine.tracer.warn(warning!(
+            span, "opacity converts CMYK colors to RGB";
+            hint: "set the opacity to 100% to keep CMYK colors in the PDF"
+        ));
+    }
+}
+
+/// Position the translucent layers that approximate a soft-edged shape, each
+/// given by how much it grows beyond the shape's outline and its paint. The
+/// shape covers the given area, that is, a position and a size.
+fn soft_items(
+    kind: ShapeKind,
+    (pos, size): (Point, Size),
+    radius: Corners<Radius<Rel<Abs>>>,
+    hole: Option<Abs>,
+    smoothing: f64,
+    layers: &[(Abs, Paint)],
+    span: Span,
+) -> Vec<(Point, FrameItem)> {
+    let mut items = vec![];
+    for (grow, paint) in layers {
+        // The inner layers of a shape that is smaller than its blur would
+        // shrink beyond zero, so they are left out.
+        let size = size + Size::splat(2.0 * *grow);
+        if size.x <= Abs::zero() || size.y <= Abs::zero() {
+            continue;
+        }
+
+        let pos = pos - Point::splat(*grow);
+        let hole = hole.map(|hole| hole - *grow).filter(|hole| *hole > Abs::zero());
+        let shapes = if let Some(hole) = hole {
+            vec![Geometry::Path(ring_path(size, hole)).filled(paint.clone())]
+        } else if kind.is_round() {
+            vec![ellipse(size, Some(paint.clone()), None)]
         } else {
-            frame.fill_and_stroke(fill, stroke, outset, radius, span);
+            let radius = radius.map(|radius| radius.map(|v| v + *grow));
+            styled_rect(size, radius, Some(paint.clone()), Sides::splat(None))
+                .into_iter()
+                .map(|shape| smooth_shape(shape, smoothing))
+                .collect()
+        };
+        items
+            .extend(shapes.into_iter().map(|shape| (pos, FrameItem::Shape(shape, span))));
+    }
+    items
+}
+
+/// Position the shapes that make up a shape's fill and stroke.
+///
+/// A stroke that changes between sides is painted with one shape per segment,
+/// so a gradient or pattern relative to the shape itself would start over on
+/// every segment. In that case, the shapes are grouped into a hard frame of
+/// the whole shape's size and their paints are made relative to that frame.
+pub(crate) fn outline_items(
+    shapes: Vec<Shape>,
+    pos: Point,
+    size: Size,
+    span: Span,
+) -> Vec<(Point, FrameItem)> {
+    let paints = || {
+        shapes.iter().flat_map(|shape| {
+            shape
+                .fill
+                .iter()
+                .chain(shape.stroke.as_ref().map(|stroke| &stroke.paint))
+        })
+    };
+
+    let is_parent = |paint: &Paint| paint.relative() == Smart::Custom(RelativeTo::Parent);
+    let is_self = |paint: &Paint| !matches!(paint, Paint::Solid(_)) && !is_parent(paint);
+
+    let segments = shapes.iter().filter(|shape| shape.stroke.is_some()).count();
+    if segments <= 1 || !paints().any(is_self) || paints().any(is_parent) {
+        return shapes
+            .into_iter()
+            .map(|shape| (pos, FrameItem::Shape(shape, span)))
+            .collect();
+    }
+
+    let to_parent = |paint: Paint| match paint {
+        Paint::Solid(_) => paint,
+        Paint::Gradient(gradient) => {
+            Paint::Gradient(gradient.with_relative(RelativeTo::Parent))
+        }
+        Paint::Pattern(pattern) => {
+            Paint::Pattern(pattern.with_relative(RelativeTo::Parent))
+        }
+    };
+
+    let mut group = Frame::hard(size);
+    for mut shape in shapes {
+        shape.fill = shape.fill.map(to_parent);
+        if let Some(stroke) = &mut shape.stroke {
+            stroke.paint = to_parent(stroke.paint.clone());
+        }
+        group.push(Point::zero(), FrameItem::Shape(shape, span));
+    }
+
+    vec![(pos, FrameItem::Group(GroupItem::new(group)))]
+}
+
+/// The position and size of the area covered by a shape with the given frame
+/// size and outset.
+///
+/// Negative outsets shrink the area. If they shrink it beyond zero along an
+/// axis, it collapses to zero at the middle of the shrunk edges.
+pub(crate) fn outset_area(size: Size, outset: Sides<Rel<Abs>>) -> (Point, Size) {
+    let outset = outset.relative_to(size);
+    let collapse = |start: Abs, end: Abs| {
+        if end < start {
+            ((start + end) / 2.0, Abs::zero())
+        } else {
+            (start, end - start)
+        }
+    };
+
+    let (x, width) = collapse(-outset.left, size.x + outset.right);
+    let (y, height) = collapse(-outset.top, size.y + outset.bottom);
+    (Point::new(x, y), Size::new(width, height))
+}
+
+/// Grow or shrink a shape's outset such that each side's stroke is placed
+/// inside of, centered on, or outside of the shape's boundary.
+fn aligned_outset(
+    outset: Sides<Rel<Abs>>,
+    stroke: &Smart<Sides<Option<Stroke<Abs>>>>,
+) -> Sides<Rel<Abs>> {
+    let Smart::Custom(strokes) = stroke else { return outset };
+    outset.zip(strokes.as_ref()).map(|(side, stroke)| {
+        side + stroke.as_ref().map_or(Abs::zero(), |stroke| {
+            let thickness = stroke.thickness.unwrap_or(FixedStroke::default().thickness);
+            stroke.align.unwrap_or_default().offset(thickness)
+        })
+    })
+}
+
+/// Resolve the minimum and maximum size of a shape.
+///
+/// For squares and circles, the limits of both axes are combined into limits
+/// for the common side length.
+fn resolve_limits(
+    styles: StyleChain,
+    regions: Regions,
+    kind: ShapeKind,
+    min: Axes<Option<Rel<Length>>>,
+    max: Axes<Option<Rel<Length>>>,
+    span: Span,
+) -> SourceResult<(Size, Size)> {
+    let resolve = |limit: Axes<Option<Rel<Length>>>, default: Abs| {
+        limit.zip_map(regions.base(), |limit, base| {
+            limit.map_or(default, |v| v.resolve(styles).relative_to(base))
+        })
+    };
+
+    let mut min = resolve(min, Abs::zero());
+    let mut max = resolve(max, Abs::inf());
+    if min.x > max.x {
+        bail!(span, "minimum width must not exceed maximum width");
+    }
+    if min.y > max.y {
+        bail!(span, "minimum height must not exceed maximum height");
+    }
+
+    if kind.is_quadratic() {
+        min = Size::splat(min.max_by_side());
+        max = Size::splat(max.min_by_side());
+        if min.x > max.x {
+            bail!(span, "minimum size must not exceed maximum size");
         }
     }
 
-    // Apply metadata.
-    frame.meta(styles, false);
+    Ok((min, max))
+}
 
-    Ok(Fragment::frame(frame))
+/// The side length into which an automatically sized square or circle must be
+/// laid out again, given the size of its first, unexpanded layout.
+///
+/// Returns `None` if the first layout is already a square of that length. An
+/// empty square is always laid out again because a negative inset may have
+/// collapsed it around its content, which would then be misplaced.
+fn square_relayout(size: Size, region: Size, min: Size, max: Size) -> Option<Abs> {
+    let length = size.max_by_side().min(region.min_by_side());
+    let length = length.max(min.x).min(max.x);
+    (size != Size::splat(length) || length.is_zero()).then_some(length)
+}
+
+/// The smallest size with the given aspect ratio that contains `size`.
+fn grow_to_aspect(size: Size, aspect: f64) -> Size {
+    if size.x < size.y * aspect {
+        Size::new(size.y * aspect, size.y)
+    } else {
+        Size::new(size.x, size.x / aspect)
+    }
+}
+
+/// The largest size with the given aspect ratio that fits into `size`.
+fn shrink_to_aspect(size: Size, aspect: f64) -> Size {
+    if size.x > size.y * aspect {
+        Size::new(size.y * aspect, size.y)
+    } else {
+        Size::new(size.x, size.x / aspect)
+    }
 }
 
 /// A category of shape.
@@ -555,6 +2516,382 @@ impl ShapeKind {
     }
 }
 
+/// An element that lays out as one of the basic shapes.
+///
+/// This allows passes that walk the content tree to inspect shapes without
+/// matching on each shape element, e.g. through
+/// `content.with::<dyn BasicShape>()`.
+pub trait BasicShape {
+    /// The kind of shape this element lays out as.
+    fn kind(&self) -> ShapeKind;
+
+    /// The content placed into the shape, if any.
+    fn body(&self, styles: StyleChain) -> Option<Content>;
+
+    /// The shape's width and height.
+    fn sizing(&self, styles: StyleChain) -> Axes<Sizing>;
+
+    /// The amount by which the shape's baseline is shifted.
+    fn baseline(&self, styles: StyleChain) -> Smart<Rel<Abs>>;
+
+    /// The size the shape takes on with an automatic width and height in a
+    /// region of the given base size.
+    ///
+    /// This is the size of the shape's content plus its inset or, without
+    /// content, its default size, within the shape's size limits. The shape
+    /// is measured as a whole, even if it is breakable and would not fit
+    /// into a single region. Like measuring content, this does not affect the
+    /// locations of the actual layout.
+    fn natural_size(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        base: Size,
+    ) -> SourceResult<Size>;
+}
+
+impl BasicShape for RectElem {
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::Rect
+    }
+
+    fn body(&self, styles: StyleChain) -> Option<Content> {
+        RectElem::body(self, styles)
+    }
+
+    fn sizing(&self, styles: StyleChain) -> Axes<Sizing> {
+        Axes::new(self.width(styles), self.height(styles))
+    }
+
+    fn baseline(&self, styles: StyleChain) -> Smart<Rel<Abs>> {
+        RectElem::baseline(self, styles)
+    }
+
+    fn natural_size(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        base: Size,
+    ) -> SourceResult<Size> {
+        let style = self.shape_style(styles);
+        natural_size(engine, styles, base, &self.body(styles), style, self.span())
+    }
+}
+
+impl BasicShape for SquareElem {
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::Square
+    }
+
+    fn body(&self, styles: StyleChain) -> Option<Content> {
+        SquareElem::body(self, styles)
+    }
+
+    fn sizing(&self, styles: StyleChain) -> Axes<Sizing> {
+        Axes::new(self.width(styles), self.height(styles))
+    }
+
+    fn baseline(&self, styles: StyleChain) -> Smart<Rel<Abs>> {
+        SquareElem::baseline(self, styles)
+    }
+
+    fn natural_size(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        base: Size,
+    ) -> SourceResult<Size> {
+        let style = self.shape_style(styles);
+        natural_size(engine, styles, base, &self.body(styles), style, self.span())
+    }
+}
+
+impl BasicShape for EllipseElem {
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::Ellipse
+    }
+
+    fn body(&self, styles: StyleChain) -> Option<Content> {
+        EllipseElem::body(self, styles)
+    }
+
+    fn sizing(&self, styles: StyleChain) -> Axes<Sizing> {
+        Axes::new(self.width(styles), self.height(styles))
+    }
+
+    fn baseline(&self, styles: StyleChain) -> Smart<Rel<Abs>> {
+        EllipseElem::baseline(self, styles)
+    }
+
+    fn natural_size(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        base: Size,
+    ) -> SourceResult<Size> {
+        let style = self.shape_style(styles);
+        natural_size(engine, styles, base, &self.body(styles), style, self.span())
+    }
+}
+
+impl BasicShape for CircleElem {
+    fn kind(&self) -> ShapeKind {
+        ShapeKind::Circle
+    }
+
+    fn body(&self, styles: StyleChain) -> Option<Content> {
+        CircleElem::body(self, styles)
+    }
+
+    fn sizing(&self, styles: StyleChain) -> Axes<Sizing> {
+        Axes::new(self.width(styles), self.height(styles))
+    }
+
+    fn baseline(&self, styles: StyleChain) -> Smart<Rel<Abs>> {
+        CircleElem::baseline(self, styles)
+    }
+
+    fn natural_size(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        base: Size,
+    ) -> SourceResult<Size> {
+        let style = self.shape_style(styles);
+        natural_size(engine, styles, base, &self.body(styles), style, self.span())
+    }
+}
+
+/// How to fit content into a round shape.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum InsetMode {
+    /// Fit the content into the largest rectangle inscribed in the shape.
+    #[default]
+    Circle,
+    /// Only apply the given inset.
+    Tight,
+}
+
+impl InsetMode {
+    /// Adjust the inset of a round shape according to this mode.
+    fn apply(self, inset: Sides<Rel<Abs>>) -> Sides<Rel<Abs>> {
+        match self {
+            Self::Circle => inset.map(|side| side + Ratio::new(0.5 - SQRT_2 / 4.0)),
+            Self::Tight => inset,
+        }
+    }
+}
+
+/// How to fill a shape.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum Fill {
+    /// Fill the shape with a paint.
+    Paint(Paint),
+    /// Fill the shape with an image that is fitted to the shape's size.
+    Image(ImageElem),
+    /// Determine the fill from the shape's size.
+    Func(Func),
+    /// Fill the shape with multiple paints, from bottom to top.
+    Layers(Vec<Paint>),
+    /// Fill the shape with a paint of the theme.
+    Token(ThemePaint),
+}
+
+impl Fill {
+    /// Look up a paint of the theme, leaving other fills unchanged.
+    fn resolve_token(self, styles: StyleChain, span: Span) -> SourceResult<Self> {
+        match self {
+            Self::Token(token) => token.resolve(styles, span).map(Self::Paint),
+            fill => Ok(fill),
+        }
+    }
+}
+
+impl<T: Into<Paint>> From<T> for Fill {
+    fn from(paint: T) -> Self {
+        Self::Paint(paint.into())
+    }
+}
+
+cast! {
+    Fill,
+    self => match self {
+        Self::Paint(paint) => paint.into_value(),
+        Self::Image(image) => image.pack().into_value(),
+        Self::Func(func) => func.into_value(),
+        Self::Layers(paints) => paints.into_value(),
+        Self::Token(token) => token.into_value(),
+    },
+    paint: Paint => Self::Paint(paint),
+    token: ThemePaint => Self::Token(token),
+    func: Func => Self::Func(func),
+    array: Array => {
+        let mut paints: Vec<Paint> =
+            array.into_iter().map(Value::cast).collect::<StrResult<_>>()?;
+        match paints.len() {
+            0 => bail!("expected at least one paint"),
+            1 => Self::Paint(paints.remove(0)),
+            _ => Self::Layers(paints),
+        }
+    },
+    content: Content => Self::Image(
+        content.to::<ImageElem>().ok_or("expected paint or image")?.clone()
+    ),
+}
+
+/// The grid to which a shape's height is rounded up.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Snap<T: Numeric = Length> {
+    /// Don't round the height.
+    #[default]
+    None,
+    /// Round to the distance between the baselines of two lines of a
+    /// paragraph.
+    Lines,
+    /// Round to multiples of the given unit.
+    Unit(T),
+}
+
+impl Resolve for Snap {
+    type Output = Snap<Abs>;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        match self {
+            Self::None => Snap::None,
+            Self::Lines => Snap::Lines,
+            Self::Unit(unit) => Snap::Unit(unit.resolve(styles)),
+        }
+    }
+}
+
+cast! {
+    Snap,
+    self => match self {
+        Self::None => false.into_value(),
+        Self::Lines => true.into_value(),
+        Self::Unit(unit) => unit.into_value(),
+    },
+    v: bool => if v { Self::Lines } else { Self::None },
+    unit: Length => Self::Unit(unit),
+}
+
+/// The radius of a rounded corner.
+///
+/// A circular radius gives a quarter circle, while an elliptical radius gives
+/// a quarter ellipse. An elliptical radius stays elliptical even if its two
+/// components are equal, because they are relative to different sides.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Radius<T> {
+    /// The horizontal radius.
+    pub x: T,
+    /// The vertical radius.
+    pub y: T,
+    /// Whether the radius was given as a single value.
+    circular: bool,
+}
+
+impl<T> Radius<T> {
+    /// Create a new elliptical radius from its two components.
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y, circular: false }
+    }
+
+    /// Create a circular radius.
+    pub fn circular(radius: T) -> Self
+    where
+        T: Clone,
+    {
+        Self { x: radius.clone(), y: radius, circular: true }
+    }
+
+    /// Map the individual components with `f`.
+    pub fn map<F, U>(self, mut f: F) -> Radius<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        Radius {
+            x: f(self.x),
+            y: f(self.y),
+            circular: self.circular,
+        }
+    }
+
+    /// Whether the radius is circular rather than elliptical.
+    pub fn is_circular(&self) -> bool {
+        self.circular
+    }
+}
+
+impl<T: Default + Clone> Default for Radius<T> {
+    fn default() -> Self {
+        Self::circular(T::default())
+    }
+}
+
+impl Radius<Rel<Abs>> {
+    /// Whether this radius describes a sharp corner.
+    pub fn is_zero(&self) -> bool {
+        self.x.is_zero() || self.y.is_zero()
+    }
+
+    /// Evaluate the radius relative to the given `size`.
+    ///
+    /// A circular radius is relative to the shorter side, while the
+    /// components of an elliptical radius are relative to the width and
+    /// height, respectively.
+    pub fn relative_to(self, size: Size) -> Radius<Abs> {
+        if self.is_circular() {
+            Radius::circular(self.x.relative_to(size.min_by_side()))
+        } else {
+            Radius::new(self.x.relative_to(size.x), self.y.relative_to(size.y))
+        }
+    }
+}
+
+impl Corners<Radius<Rel<Abs>>> {
+    /// Evaluate the corner radii relative to the given `size`.
+    pub fn relative_to(self, size: Size) -> Corners<Radius<Abs>> {
+        self.map(|radius| radius.relative_to(size))
+    }
+}
+
+impl<T: Resolve> Resolve for Radius<T> {
+    type Output = Radius<T::Output>;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        self.map(|v| v.resolve(styles))
+    }
+}
+
+impl<T> Fold for Radius<T> {
+    type Output = Self;
+
+    fn fold(self, _: Self::Output) -> Self::Output {
+        self
+    }
+}
+
+cast! {
+    Radius<Rel<Length>>,
+    self => if self.is_circular() {
+        self.x.into_value()
+    } else {
+        dict! { "x" => self.x, "y" => self.y }.into_value()
+    },
+    v: Rel<Length> => Self::circular(v),
+    mut dict: Dict => {
+        let x = dict.take("x")?.cast()?;
+        let y = dict.take("y")?.cast()?;
+        dict.finish(&["x", "y"])?;
+        Self::new(x, y)
+    },
+}
+
+cast! {
+    Radius<Rel<Abs>>,
+    self => self.map(|v| v.map(Length::from)).into_value(),
+}
+
 /// A geometric shape with optional fill and stroke.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Shape {
@@ -564,38 +2901,157 @@ pub struct Shape {
     pub fill: Option<Paint>,
     /// The shape's border stroke.
     pub stroke: Option<FixedStroke>,
+    /// How overlapping regions of the outline are filled.
+    pub fill_rule: FillRule,
+}
+
+impl Shape {
+    /// Whether the given point, relative to the shape's origin, lies inside of
+    /// the shape's outline or on its stroke.
+    ///
+    /// The inside of a rectangle or path counts regardless of whether the
+    /// shape is filled, so rounded corners and curves are respected exactly.
+    /// Self-intersecting outlines follow the shape's fill rule. Open paths are
+    /// treated as if they were closed. A line only contains the points on its
+    /// stroke. Points on the outline itself are always contained.
+    pub fn contains(&self, point: Point) -> bool {
+        let path = match &self.geometry {
+            Geometry::Line(end) => {
+                let mut path = Path::new();
+                path.move_to(Point::zero());
+                path.line_to(*end);
+                path
+            }
+            Geometry::Rect(size) => Path::rect(*size),
+            Geometry::Path(path) => path.clone(),
+            Geometry::Polygon(points) => Path::polygon(points),
+        };
+
+        let path = to_bez_path(&path);
+        let point = kurbo::Point::new(point.x.to_raw(), point.y.to_raw());
+        let winding = path.winding(point);
+        let inside = match self.fill_rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        };
+        if !matches!(self.geometry, Geometry::Line(_)) && inside {
+            return true;
+        }
+
+        // Stroked points are within half the thickness of the outline.
+        let reach = self
+            .stroke
+            .as_ref()
+            .map_or(Abs::zero(), |stroke| stroke.thickness / 2.0)
+            .to_raw()
+            + 1e-6;
+        let mut segments = path.segments();
+        segments.any(|segment| segment.nearest(point, 1e-6).distance_sq <= reach * reach)
+    }
+}
+
+/// Convert a path into a kurbo path.
+fn to_bez_path(path: &Path) -> kurbo::BezPath {
+    let point = |p: &Point| kurbo::Point::new(p.x.to_raw(), p.y.to_raw());
+    let mut bez = kurbo::BezPath::new();
+    for item in &path.0 {
+        match item {
+            PathItem::MoveTo(p) => bez.move_to(point(p)),
+            PathItem::LineTo(p) => bez.line_to(point(p)),
+            PathItem::CubicTo(a, b, p) => bez.curve_to(point(a), point(b), point(p)),
+            PathItem::ClosePath => bez.close_path(),
+        }
+    }
+    bez
 }
 
 /// A shape's geometry.
+///
+/// All coordinates are absolute, so exporters don't need the style chain.
+/// Relative sizes and corner radii are resolved during layout.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Geometry {
     /// A line to a point (relative to its position).
     Line(Point),
-    /// A rectangle with its origin in the topleft corner.
+    /// A rectangle with sharp corners and its origin in the topleft corner.
+    /// Rounded rectangles are paths with their resolved corner arcs.
     Rect(Size),
     /// A bezier path.
     Path(Path),
+    /// A closed polygon through the given vertices (relative to its
+    /// position). The last vertex connects back to the first one.
+    Polygon(Vec<Point>),
+}
+
+/// A rule that decides which regions of a shape's outline count as inside.
+///
+/// This only matters for self-intersecting paths and polygons, like stars and
+/// rings. Rectangles and ellipses look the same with either rule.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum FillRule {
+    /// A point is inside if the outline winds around it a non-zero number of
+    /// times, counting clockwise and counter-clockwise windings oppositely.
+    #[default]
+    #[string("nonzero")]
+    NonZero,
+    /// A point is inside if any ray from it crosses the outline an odd number
+    /// of times.
+    #[string("evenodd")]
+    EvenOdd,
 }
 
 impl Geometry {
     /// Fill the geometry without a stroke.
     pub fn filled(self, fill: Paint) -> Shape {
-        Shape { geometry: self, fill: Some(fill), stroke: None }
+        Shape {
+            geometry: self,
+            fill: Some(fill),
+            stroke: None,
+            fill_rule: FillRule::default(),
+        }
     }
 
     /// Stroke the geometry without a fill.
     pub fn stroked(self, stroke: FixedStroke) -> Shape {
-        Shape { geometry: self, fill: None, stroke: Some(stroke) }
+        Shape {
+            geometry: self,
+            fill: None,
+            stroke: Some(stroke),
+            fill_rule: FillRule::default(),
+        }
     }
 
-    /// The bounding box of the geometry.
-    pub fn bbox_size(&self) -> Size {
+    /// The top-left corner and size of the geometry's axis-aligned bounding
+    /// box, relative to the geometry's position.
+    ///
+    /// Rounded corners don't change the bounding box of a rectangle, which is
+    /// why it is the same for a plain and a rounded rectangle of the same
+    /// size.
+    pub fn bbox(&self) -> (Point, Size) {
         match self {
-            Self::Line(line) => Size::new(line.x, line.y),
-            Self::Rect(s) => *s,
-            Self::Path(p) => p.bbox_size(),
+            Self::Line(end) => {
+                (end.min(Point::zero()), Size::new(end.x.abs(), end.y.abs()))
+            }
+            Self::Rect(s) => (Point::zero(), *s),
+            Self::Path(p) => p.bbox(),
+            Self::Polygon(points) => {
+                let Some(&first) = points.first() else {
+                    return (Point::zero(), Size::zero());
+                };
+                let (min, max) = points
+                    .iter()
+                    .fold((first, first), |(min, max), &p| (min.min(p), max.max(p)));
+                (min, (max - min).to_size())
+            }
         }
     }
+
+    /// The size of the geometry's bounding box.
+    ///
+    /// This is the size returned by [`bbox`](Self::bbox).
+    pub fn bbox_size(&self) -> Size {
+        self.bbox().1
+    }
 }
 
 /// Produce a shape that approximates an axis-aligned ellipse.
@@ -604,6 +3060,16 @@ pub(crate) fn ellipse(
     fill: Option<Paint>,
     stroke: Option<FixedStroke>,
 ) -> Shape {
+    Shape {
+        geometry: Geometry::Path(ellipse_path(size)),
+        stroke,
+        fill,
+        fill_rule: FillRule::default(),
+    }
+}
+
+/// Creates a new ellipse as a path.
+fn ellipse_path(size: Size) -> Path {
     // https://stackoverflow.com/a/2007782
     let z = Abs::zero();
     let rx = size.x / 2.0;
@@ -619,25 +3085,145 @@ pub(crate) fn ellipse(
     path.cubic_to(point(mx, -ry), point(rx, -my), point(rx, z));
     path.cubic_to(point(rx, my), point(mx, ry), point(z, ry));
     path.cubic_to(point(-mx, ry), point(-rx, my), point(-rx, z));
+    path.close_path();
+    path
+}
+
+/// Creates a new ring as a path: an ellipse with a concentric circular hole.
+///
+/// The hole winds the other way than the ellipse, so that it stays empty under
+/// both the nonzero and the even-odd fill rule.
+fn ring_path(size: Size, hole: Abs) -> Path {
+    let mut path = ellipse_path(size);
+
+    // Mirroring the hole vertically reverses its direction.
+    let inner = Size::splat(2.0 * hole);
+    let offset = ((size - inner) / 2.0).to_point();
+    let mirror = |p: Point| Point::new(p.x, inner.y - p.y) + offset;
+    for item in ellipse_path(inner).0 {
+        path.0.push(match item {
+            PathItem::MoveTo(p) => PathItem::MoveTo(mirror(p)),
+            PathItem::LineTo(p) => PathItem::LineTo(mirror(p)),
+            PathItem::CubicTo(a, b, p) => {
+                PathItem::CubicTo(mirror(a), mirror(b), mirror(p))
+            }
+            PathItem::ClosePath => PathItem::ClosePath,
+        });
+    }
+    path
+}
+
+/// Create a styled ring with shapes.
+/// - use a single shape if all sides are stroked the same
+/// - otherwise stroke the outer and inner edge like those of an ellipse
+fn styled_ring(
+    size: Size,
+    hole: Abs,
+    fill: Option<Paint>,
+    stroke: Sides<Option<FixedStroke>>,
+) -> Vec<Shape> {
+    let path = ring_path(size, hole);
+    if stroke.is_uniform() {
+        return vec![Shape {
+            geometry: Geometry::Path(path),
+            fill,
+            stroke: stroke.top,
+            fill_rule: FillRule::default(),
+        }];
+    }
+
+    let mut res = vec![];
+    if let Some(fill) = fill {
+        res.push(Geometry::Path(path).filled(fill));
+    }
+
+    let inner = Size::splat(2.0 * hole);
+    let offset = ((size - inner) / 2.0).to_point();
+    res.extend(styled_ellipse(size, None, stroke.clone()));
+    res.extend(styled_ellipse(inner, None, stroke).into_iter().map(|mut shape| {
+        if let Geometry::Path(path) = &mut shape.geometry {
+            path.translate(offset);
+        }
+        shape
+    }));
+    res
+}
 
-    Shape { geometry: Geometry::Path(path), stroke, fill }
+/// Creates a new ellipse as a path that runs along the inner edge of the
+/// stroke.
+fn clip_ellipse(size: Size, stroke: &Sides<Option<FixedStroke>>) -> Path {
+    let stroke_widths = stroke
+        .as_ref()
+        .map(|s| s.as_ref().map_or(Abs::zero(), |s| s.thickness / 2.0));
+    let inner = (size - stroke_widths.sum_by_axis()).max(Size::zero());
+    let mut path = ellipse_path(inner);
+    path.translate(Point::new(stroke_widths.left, stroke_widths.top));
+    path
+}
+
+/// Create a styled ellipse with shapes.
+/// - use a single shape if all sides are stroked the same
+/// - otherwise stroke each run of equally stroked sides as an open arc
+fn styled_ellipse(
+    size: Size,
+    fill: Option<Paint>,
+    stroke: Sides<Option<FixedStroke>>,
+) -> Vec<Shape> {
+    if stroke.is_uniform() {
+        return vec![ellipse(size, fill, stroke.top)];
+    }
+
+    let mut res = vec![];
+    if let Some(fill) = fill {
+        res.push(ellipse(size, Some(fill), None));
+    }
+
+    // The sides in clockwise order, each spanning a quarter of the ellipse
+    // that starts at the given angle.
+    let sides = [
+        (-0.75, stroke.top),
+        (-0.25, stroke.right),
+        (0.25, stroke.bottom),
+        (0.75, stroke.left),
+    ];
+
+    // Start at a side whose predecessor is stroked differently, so that runs
+    // of equally stroked sides are drawn as a single continuous arc.
+    let first = (0..4).find(|&i| sides[i].1 != sides[(i + 3) % 4].1).unwrap_or(0);
+    let center = (size / 2.0).to_point();
+    let radius = size / 2.0;
+    let mut i = 0;
+    while i < 4 {
+        let (start, stroke) = &sides[(first + i) % 4];
+        let mut end = start + 0.5;
+        i += 1;
+        while i < 4 && sides[(first + i) % 4].1 == *stroke {
+            end += 0.5;
+            i += 1;
+        }
+
+        if let Some(stroke) = stroke {
+            let mut path = Path::new();
+            path.move_to(ellipse_arc_point(center, radius, *start * PI));
+            path.ellipse_arc(center, radius, *start * PI, end * PI);
+            res.push(Geometry::Path(path).stroked(stroke.clone()));
+        }
+    }
+
+    res
 }
 
 /// Creates a new rectangle as a path.
 pub(crate) fn clip_rect(
     size: Size,
-    radius: Corners<Rel<Abs>>,
+    radius: Corners<Radius<Rel<Abs>>>,
     stroke: &Sides<Option<FixedStroke>>,
 ) -> Path {
     let stroke_widths = stroke
         .as_ref()
         .map(|s| s.as_ref().map_or(Abs::zero(), |s| s.thickness / 2.0));
 
-    let max_radius = (size.x.min(size.y)) / 2.0
-        + stroke_widths.iter().cloned().min().unwrap_or(Abs::zero());
-
-    let radius = radius.map(|side| side.relative_to(max_radius * 2.0).min(max_radius));
-
+    let radius = resolve_radius(size, radius, stroke_widths);
     let corners = corners_control_points(size, radius, stroke, stroke_widths);
 
     let mut path = Path::new();
@@ -645,6 +3231,7 @@ pub(crate) fn clip_rect(
         path.arc_move(
             corners.top_left.start_inner(),
             corners.top_left.center_inner(),
+            corners.top_left.radius_inner(),
             corners.top_left.end_inner(),
         );
     } else {
@@ -652,7 +3239,12 @@ pub(crate) fn clip_rect(
     }
     for corner in [&corners.top_right, &corners.bottom_right, &corners.bottom_left] {
         if corner.arc_inner() {
-            path.arc_line(corner.start_inner(), corner.center_inner(), corner.end_inner())
+            path.arc_line(
+                corner.start_inner(),
+                corner.center_inner(),
+                corner.radius_inner(),
+                corner.end_inner(),
+            )
         } else {
             path.line_to(corner.center_inner());
         }
@@ -665,13 +3257,18 @@ pub(crate) fn clip_rect(
 /// - use rect primitive for simple rectangles
 /// - stroke sides if possible
 /// - use fill for sides for best looks
+///
+/// The stroke is uniform if all sides have exactly the same stroke, that is,
+/// the same paint, thickness, cap, join, dash pattern, and miter limit, or
+/// if no side is stroked. Any difference, even just in paint, draws the
+/// sides as separate segments.
 pub(crate) fn styled_rect(
     size: Size,
-    radius: Corners<Rel<Abs>>,
+    radius: Corners<Radius<Rel<Abs>>>,
     fill: Option<Paint>,
     stroke: Sides<Option<FixedStroke>>,
 ) -> Vec<Shape> {
-    if stroke.is_uniform() && radius.iter().cloned().all(Rel::is_zero) {
+    if stroke.is_uniform() && radius.iter().all(Radius::is_zero) {
         simple_rect(size, fill, stroke.top)
     } else {
         segmented_rect(size, radius, fill, stroke)
@@ -684,12 +3281,107 @@ fn simple_rect(
     fill: Option<Paint>,
     stroke: Option<FixedStroke>,
 ) -> Vec<Shape> {
-    vec![Shape { geometry: Geometry::Rect(size), fill, stroke }]
+    vec![Shape {
+        geometry: Geometry::Rect(size),
+        fill,
+        stroke,
+        fill_rule: FillRule::default(),
+    }]
+}
+
+/// Smooth the rounded corners of a rectangle's shape. See [`smooth_corners`].
+fn smooth_shape(mut shape: Shape, smoothing: f64) -> Shape {
+    if let Geometry::Path(path) = shape.geometry {
+        shape.geometry = Geometry::Path(smooth_corners(path, smoothing));
+    }
+    shape
+}
+
+/// Smooth the rounded corners of a rectangle's path into superellipses.
+///
+/// The curves of a rectangle's path are exactly its (partial) corner arcs.
+/// Lengthening their control handles pulls the curves towards the corners,
+/// such that with full smoothing, a quarter arc approximates a quarter of the
+/// superellipse `|x|^4 + |y|^4 = 1` instead of a circle.
+fn smooth_corners(mut path: Path, smoothing: f64) -> Path {
+    // The handle lengths of a bezier curve approximating a quarter circle and
+    // one approximating a quarter of the superellipse, relative to the radius.
+    // Both curves hit their exact midpoint at 45 degrees.
+    const CIRCLE: f64 = 0.552_284_749_830_793_4;
+    const SQUIRCLE: f64 = 0.909_121_094_816_454_7;
+
+    if smoothing <= 0.0 {
+        return path;
+    }
+
+    let factor = 1.0 + smoothing * (SQUIRCLE / CIRCLE - 1.0);
+    let mut start = Point::zero();
+    let mut current = Point::zero();
+    for item in &mut path.0 {
+        match item {
+            PathItem::MoveTo(point) => {
+                start = *point;
+                current = *point;
+            }
+            PathItem::LineTo(point) => current = *point,
+            PathItem::CubicTo(a, b, end) => {
+                *a = current + (*a - current) * factor;
+                *b = *end + (*b - *end) * factor;
+                current = *end;
+            }
+            PathItem::ClosePath => current = start,
+        }
+    }
+    path
+}
+
+/// Resolve the corner radii relative to the rectangle's size.
+///
+/// If the radii of adjacent corners overlap along a side, all radii are scaled
+/// by the same factor such that they fit along every side, following the CSS
+/// rules for `border-radius`.
+fn resolve_radius(
+    size: Size,
+    radius: Corners<Radius<Rel<Abs>>>,
+    stroke_widths: Sides<Abs>,
+) -> Corners<Radius<Abs>> {
+    let stroke = stroke_widths.iter().cloned().min().unwrap_or(Abs::zero());
+    let available = size + Size::splat(2.0 * stroke);
+    let radius = radius.map(|radius| radius.relative_to(available));
+
+    // The factor by which the radii must shrink to fit along each side.
+    let fit = |side: Abs, a: Abs, b: Abs| {
+        let sum = a + b;
+        if sum > side {
+            side / sum
+        } else {
+            1.0
+        }
+    };
+    let factor = [
+        fit(available.x, radius.top_left.x, radius.top_right.x),
+        fit(available.x, radius.bottom_left.x, radius.bottom_right.x),
+        fit(available.y, radius.top_left.y, radius.bottom_left.y),
+        fit(available.y, radius.top_right.y, radius.bottom_right.y),
+    ]
+    .into_iter()
+    .fold(1.0, f64::min);
+
+    radius.map(|radius| {
+        let radius = radius.map(|v| v * factor);
+
+        // An elliptical arc with only one nonzero radius is a sharp corner.
+        if radius.x > Abs::zero() && radius.y > Abs::zero() {
+            radius
+        } else {
+            Radius::default()
+        }
+    })
 }
 
 fn corners_control_points(
     size: Size,
-    radius: Corners<Abs>,
+    radius: Corners<Radius<Abs>>,
     strokes: &Sides<Option<FixedStroke>>,
     stroke_widths: Sides<Abs>,
 ) -> Corners<ControlPoints> {
@@ -700,7 +3392,15 @@ fn corners_control_points(
         bottom_left: Corner::BottomLeft,
     }
     .map(|corner| ControlPoints {
-        radius: radius.get(corner),
+        // Control points are computed as if for the top-left corner and then
+        // rotated into place, which swaps the axes for two of the corners.
+        radius: {
+            let radius = radius.get(corner);
+            match corner {
+                Corner::TopLeft | Corner::BottomRight => Size::new(radius.x, radius.y),
+                Corner::TopRight | Corner::BottomLeft => Size::new(radius.y, radius.x),
+            }
+        },
         stroke_before: stroke_widths.get(corner.side_ccw()),
         stroke_after: stroke_widths.get(corner.side_cw()),
         corner,
@@ -709,7 +3409,11 @@ fn corners_control_points(
             strokes.get_ref(corner.side_ccw()),
             strokes.get_ref(corner.side_cw()),
         ) {
-            (Some(a), Some(b)) => a.paint == b.paint && a.dash_pattern == b.dash_pattern,
+            (Some(a), Some(b)) => {
+                a.paint == b.paint
+                    && a.dash_pattern == b.dash_pattern
+                    && a.line_cap == b.line_cap
+            }
             (None, None) => true,
             _ => false,
         },
@@ -719,7 +3423,7 @@ fn corners_control_points(
 /// Use stroke and fill for the rectangle
 fn segmented_rect(
     size: Size,
-    radius: Corners<Rel<Abs>>,
+    radius: Corners<Radius<Rel<Abs>>>,
     fill: Option<Paint>,
     strokes: Sides<Option<FixedStroke>>,
 ) -> Vec<Shape> {
@@ -728,11 +3432,7 @@ fn segmented_rect(
         .as_ref()
         .map(|s| s.as_ref().map_or(Abs::zero(), |s| s.thickness / 2.0));
 
-    let max_radius = (size.x.min(size.y)) / 2.0
-        + stroke_widths.iter().cloned().min().unwrap_or(Abs::zero());
-
-    let radius = radius.map(|side| side.relative_to(max_radius * 2.0).min(max_radius));
-
+    let radius = resolve_radius(size, radius, stroke_widths);
     let corners = corners_control_points(size, radius, &strokes, stroke_widths);
 
     // insert stroked sides below filled sides
@@ -743,7 +3443,7 @@ fn segmented_rect(
         let mut path = Path::new();
         let c = corners.get_ref(Corner::TopLeft);
         if c.arc() {
-            path.arc_move(c.start(), c.center(), c.end());
+            path.arc_move(c.start(), c.center(), c.radius(), c.end());
         } else {
             path.move_to(c.center());
         };
@@ -751,7 +3451,7 @@ fn segmented_rect(
         for corner in [Corner::TopRight, Corner::BottomRight, Corner::BottomLeft] {
             let c = corners.get_ref(corner);
             if c.arc() {
-                path.arc_line(c.start(), c.center(), c.end());
+                path.arc_line(c.start(), c.center(), c.radius(), c.end());
             } else {
                 path.line_to(c.center());
             }
@@ -761,6 +3461,7 @@ fn segmented_rect(
             geometry: Geometry::Path(path),
             fill: Some(fill),
             stroke: None,
+            fill_rule: FillRule::default(),
         });
         stroke_insert += 1;
     }
@@ -810,7 +3511,7 @@ fn path_segment(
     if start == end || !c.arc() {
         path.move_to(c.end());
     } else {
-        path.arc_move(c.mid(), c.center(), c.end());
+        path.arc_move(c.mid(), c.center(), c.radius(), c.end());
     }
 
     // create corners between start and end
@@ -818,7 +3519,7 @@ fn path_segment(
     while current != end {
         let c = corners.get_ref(current);
         if c.arc() {
-            path.arc_line(c.start(), c.center(), c.end());
+            path.arc_line(c.start(), c.center(), c.radius(), c.end());
         } else {
             path.line_to(c.end());
         }
@@ -830,9 +3531,9 @@ fn path_segment(
     if !c.arc() {
         path.line_to(c.start());
     } else if start == end {
-        path.arc_line(c.start(), c.center(), c.end());
+        path.arc_line(c.start(), c.center(), c.radius(), c.end());
     } else {
-        path.arc_line(c.start(), c.center(), c.mid());
+        path.arc_line(c.start(), c.center(), c.radius(), c.mid());
     }
 }
 
@@ -844,8 +3545,10 @@ fn segment(
     stroke: FixedStroke,
 ) -> (Shape, bool) {
     fn fill_corner(corner: &ControlPoints) -> bool {
+        let radius = corner.radius_local();
         corner.stroke_before != corner.stroke_after
-            || corner.radius() < corner.stroke_before
+            || radius.x < corner.stroke_before
+            || radius.y < corner.stroke_after
     }
 
     fn fill_corners(
@@ -896,10 +3599,17 @@ fn stroke_segment(
     let mut path = Path::new();
     path_segment(start, end, corners, &mut path);
 
+    // A stroke all around is a single closed path, so that a dash pattern runs
+    // continuously along the whole outline, including the rounded corners.
+    if start == end {
+        path.close_path();
+    }
+
     Shape {
         geometry: Geometry::Path(path),
         stroke: Some(stroke),
         fill: None,
+        fill_rule: FillRule::default(),
     }
 }
 
@@ -924,13 +3634,23 @@ fn fill_segment(
         let c = corners.get_ref(start);
 
         if c.arc_inner() {
-            path.arc_move(c.end_inner(), c.center_inner(), c.mid_inner());
+            path.arc_move(
+                c.end_inner(),
+                c.center_inner(),
+                c.radius_inner(),
+                c.mid_inner(),
+            );
         } else {
             path.move_to(c.end_inner());
         }
 
         if c.arc_outer() {
-            path.arc_line(c.mid_outer(), c.center_outer(), c.end_outer());
+            path.arc_line(
+                c.mid_outer(),
+                c.center_outer(),
+                c.radius_outer(),
+                c.end_outer(),
+            );
         } else {
             path.line_to(c.outer());
             path.line_to(c.end_outer());
@@ -942,7 +3662,12 @@ fn fill_segment(
     while current != end {
         let c = corners.get_ref(current);
         if c.arc_outer() {
-            path.arc_line(c.start_outer(), c.center_outer(), c.end_outer());
+            path.arc_line(
+                c.start_outer(),
+                c.center_outer(),
+                c.radius_outer(),
+                c.end_outer(),
+            );
         } else {
             path.line_to(c.outer());
         }
@@ -956,25 +3681,45 @@ fn fill_segment(
     if start == end {
         let c = corners.get_ref(end);
         if c.arc_outer() {
-            path.arc_line(c.start_outer(), c.center_outer(), c.end_outer());
+            path.arc_line(
+                c.start_outer(),
+                c.center_outer(),
+                c.radius_outer(),
+                c.end_outer(),
+            );
         } else {
             path.line_to(c.outer());
             path.line_to(c.end_outer());
         }
         if c.arc_inner() {
-            path.arc_line(c.end_inner(), c.center_inner(), c.start_inner());
+            path.arc_line(
+                c.end_inner(),
+                c.center_inner(),
+                c.radius_inner(),
+                c.start_inner(),
+            );
         } else {
             path.line_to(c.center_inner());
         }
     } else {
         let c = corners.get_ref(end);
         if c.arc_outer() {
-            path.arc_line(c.start_outer(), c.center_outer(), c.mid_outer());
+            path.arc_line(
+                c.start_outer(),
+                c.center_outer(),
+                c.radius_outer(),
+                c.mid_outer(),
+            );
         } else {
             path.line_to(c.outer());
         }
         if c.arc_inner() {
-            path.arc_line(c.mid_inner(), c.center_inner(), c.start_inner());
+            path.arc_line(
+                c.mid_inner(),
+                c.center_inner(),
+                c.radius_inner(),
+                c.start_inner(),
+            );
         } else {
             path.line_to(c.center_inner());
         }
@@ -985,7 +3730,12 @@ fn fill_segment(
     while current != start {
         let c = corners.get_ref(current);
         if c.arc_inner() {
-            path.arc_line(c.end_inner(), c.center_inner(), c.start_inner());
+            path.arc_line(
+                c.end_inner(),
+                c.center_inner(),
+                c.radius_inner(),
+                c.start_inner(),
+            );
         } else {
             path.line_to(c.center_inner());
         }
@@ -998,6 +3748,7 @@ fn fill_segment(
         geometry: Geometry::Path(path),
         stroke: None,
         fill: Some(stroke.paint),
+        fill_rule: FillRule::default(),
     }
 }
 
@@ -1021,7 +3772,7 @@ fn fill_segment(
 ///    |-------r--------|
 /// ```
 struct ControlPoints {
-    radius: Abs,
+    radius: Size,
     stroke_after: Abs,
     stroke_before: Abs,
     corner: Corner,
@@ -1042,6 +3793,14 @@ impl ControlPoints {
         }
     }
 
+    /// Rotate a radius from top-left to the required corner.
+    fn rotate_radius(&self, radius: Size) -> Size {
+        match self.corner {
+            Corner::TopLeft | Corner::BottomRight => radius,
+            Corner::TopRight | Corner::BottomLeft => Size::new(radius.y, radius.x),
+        }
+    }
+
     /// Outside intersection of the sides.
     pub fn outer(&self) -> Point {
         self.rotate(Point { x: -self.stroke_before, y: -self.stroke_after })
@@ -1049,42 +3808,57 @@ impl ControlPoints {
 
     /// Center for the outer arc.
     pub fn center_outer(&self) -> Point {
-        let r = self.radius_outer();
+        let r = self.radius_outer_local();
         self.rotate(Point {
-            x: r - self.stroke_before,
-            y: r - self.stroke_after,
+            x: r.x - self.stroke_before,
+            y: r.y - self.stroke_after,
         })
     }
 
     /// Center for the middle arc.
     pub fn center(&self) -> Point {
-        let r = self.radius();
-        self.rotate(Point { x: r, y: r })
+        let r = self.radius_local();
+        self.rotate(Point { x: r.x, y: r.y })
     }
 
     /// Center for the inner arc.
     pub fn center_inner(&self) -> Point {
-        let r = self.radius_inner();
+        let r = self.radius_inner_local();
 
         self.rotate(Point {
-            x: self.stroke_before + r,
-            y: self.stroke_after + r,
+            x: self.stroke_before + r.x,
+            y: self.stroke_after + r.y,
         })
     }
 
-    /// Radius of the outer arc.
-    pub fn radius_outer(&self) -> Abs {
+    /// Radii of the outer arc.
+    pub fn radius_outer(&self) -> Size {
+        self.rotate_radius(self.radius_outer_local())
+    }
+
+    /// Radii of the middle arc.
+    pub fn radius(&self) -> Size {
+        self.rotate_radius(self.radius_local())
+    }
+
+    /// Radii of the inner arc.
+    pub fn radius_inner(&self) -> Size {
+        self.rotate_radius(self.radius_inner_local())
+    }
+
+    /// Radii of the outer arc, before rotation.
+    fn radius_outer_local(&self) -> Size {
         self.radius
     }
 
-    /// Radius of the middle arc.
-    pub fn radius(&self) -> Abs {
-        (self.radius - self.stroke_before.min(self.stroke_after)).max(Abs::zero())
+    /// Radii of the middle arc, before rotation.
+    fn radius_local(&self) -> Size {
+        shrink_radius(self.radius, self.stroke_before.min(self.stroke_after))
     }
 
-    /// Radius of the inner arc.
-    pub fn radius_inner(&self) -> Abs {
-        (self.radius - 2.0 * self.stroke_before.max(self.stroke_after)).max(Abs::zero())
+    /// Radii of the inner arc, before rotation.
+    fn radius_inner_local(&self) -> Size {
+        shrink_radius(self.radius, 2.0 * self.stroke_before.max(self.stroke_after))
     }
 
     /// Middle of the corner on the outside of the stroke.
@@ -1095,128 +3869,655 @@ impl ControlPoints {
         let r = self.radius_outer();
 
         // https://math.stackexchange.com/a/311956
-        // intersection between the line from inner center to outside and the outer arc
-        let a = (o.x - c_i.x).to_raw().powi(2) + (o.y - c_i.y).to_raw().powi(2);
-        let b = 2.0 * (o.x - c_i.x).to_raw() * (c_i.x - c_o.x).to_raw()
-            + 2.0 * (o.y - c_i.y).to_raw() * (c_i.y - c_o.y).to_raw();
-        let c = (c_i.x - c_o.x).to_raw().powi(2) + (c_i.y - c_o.y).to_raw().powi(2)
-            - r.to_raw().powi(2);
+        // intersection between the line from inner center to outside and the
+        // outer arc, computed in a space where the outer arc is circular
+        let scale = |p: Point| (p.x / r.x, p.y / r.y);
+        let (d, e) = (scale(o - c_i), scale(c_i - c_o));
+        let a = d.0.powi(2) + d.1.powi(2);
+        let b = 2.0 * d.0 * e.0 + 2.0 * d.1 * e.1;
+        let c = e.0.powi(2) + e.1.powi(2) - 1.0;
         let t = (-b + (b * b - 4.0 * a * c).sqrt()) / (2.0 * a);
         c_i + t * (o - c_i)
     }
 
     /// Middle of the corner in the middle of the stroke.
     pub fn mid(&self) -> Point {
-        let center = self.center_outer();
-        let outer = self.outer();
-        let diff = outer - center;
-        center + diff / diff.hypot().to_raw() * self.radius().to_raw()
+        ellipse_point(self.center_outer(), self.outer(), self.radius())
     }
 
     /// Middle of the corner on the inside of the stroke.
     pub fn mid_inner(&self) -> Point {
-        let center = self.center_inner();
-        let outer = self.outer();
-        let diff = outer - center;
-        center + diff / diff.hypot().to_raw() * self.radius_inner().to_raw()
+        ellipse_point(self.center_inner(), self.outer(), self.radius_inner())
     }
 
     /// If an outer arc is required.
     pub fn arc_outer(&self) -> bool {
-        self.radius_outer() > Abs::zero()
+        !self.radius_outer_local().is_zero()
     }
 
     pub fn arc(&self) -> bool {
-        self.radius() > Abs::zero()
+        !self.radius_local().is_zero()
     }
 
     /// If an inner arc is required.
     pub fn arc_inner(&self) -> bool {
-        self.radius_inner() > Abs::zero()
+        !self.radius_inner_local().is_zero()
     }
 
     /// Start of the corner on the outside of the stroke.
     pub fn start_outer(&self) -> Point {
         self.rotate(Point {
             x: -self.stroke_before,
-            y: self.radius_outer() - self.stroke_after,
+            y: self.radius_outer_local().y - self.stroke_after,
         })
     }
 
     /// Start of the corner in the center of the stroke.
     pub fn start(&self) -> Point {
-        self.rotate(Point::with_y(self.radius()))
+        self.rotate(Point::with_y(self.radius_local().y))
     }
 
     /// Start of the corner on the inside of the stroke.
     pub fn start_inner(&self) -> Point {
         self.rotate(Point {
             x: self.stroke_before,
-            y: self.stroke_after + self.radius_inner(),
+            y: self.stroke_after + self.radius_inner_local().y,
         })
     }
 
     /// End of the corner on the outside of the stroke.
     pub fn end_outer(&self) -> Point {
         self.rotate(Point {
-            x: self.radius_outer() - self.stroke_before,
+            x: self.radius_outer_local().x - self.stroke_before,
             y: -self.stroke_after,
         })
     }
 
     /// End of the corner in the center of the stroke.
     pub fn end(&self) -> Point {
-        self.rotate(Point::with_x(self.radius()))
+        self.rotate(Point::with_x(self.radius_local().x))
     }
 
     /// End of the corner on the inside of the stroke.
     pub fn end_inner(&self) -> Point {
         self.rotate(Point {
-            x: self.stroke_before + self.radius_inner(),
+            x: self.stroke_before + self.radius_inner_local().x,
             y: self.stroke_after,
         })
     }
 }
 
+/// Shrink both radii of an arc by the same amount. If either of them vanishes,
+/// the corner becomes sharp.
+fn shrink_radius(radius: Size, amount: Abs) -> Size {
+    let shrunk = radius - Size::splat(amount);
+    if shrunk.x > Abs::zero() && shrunk.y > Abs::zero() {
+        shrunk
+    } else {
+        Size::zero()
+    }
+}
+
+/// The point on the axis-aligned ellipse around `center` with the given radii
+/// that lies in the direction of `toward`.
+fn ellipse_point(center: Point, toward: Point, radius: Size) -> Point {
+    let diff = toward - center;
+    let t = 1.0 / ((diff.x / radius.x).powi(2) + (diff.y / radius.y).powi(2)).sqrt();
+    center + diff * t
+}
+
 /// Helper to draw arcs with bezier curves.
-trait PathExt {
-    fn arc(&mut self, start: Point, center: Point, end: Point);
-    fn arc_move(&mut self, start: Point, center: Point, end: Point);
-    fn arc_line(&mut self, start: Point, center: Point, end: Point);
+pub(crate) trait PathExt {
+    fn arc(&mut self, start: Point, center: Point, radius: Size, end: Point);
+    fn arc_move(&mut self, start: Point, center: Point, radius: Size, end: Point);
+    fn arc_line(&mut self, start: Point, center: Point, radius: Size, end: Point);
+    fn ellipse_arc(&mut self, center: Point, radius: Size, start: f64, end: f64);
 }
 
 impl PathExt for Path {
-    fn arc(&mut self, start: Point, center: Point, end: Point) {
-        let arc = bezier_arc_control(start, center, end);
+    fn arc(&mut self, start: Point, center: Point, radius: Size, end: Point) {
+        let arc = bezier_arc_control(start, center, radius, end);
         self.cubic_to(arc[0], arc[1], end);
     }
 
-    fn arc_move(&mut self, start: Point, center: Point, end: Point) {
+    fn arc_move(&mut self, start: Point, center: Point, radius: Size, end: Point) {
         self.move_to(start);
-        self.arc(start, center, end);
+        self.arc(start, center, radius, end);
     }
 
-    fn arc_line(&mut self, start: Point, center: Point, end: Point) {
+    fn arc_line(&mut self, start: Point, center: Point, radius: Size, end: Point) {
         self.line_to(start);
-        self.arc(start, center, end);
+        self.arc(start, center, radius, end);
     }
+
+    /// Continue the path along the axis-aligned ellipse from the angle `start`
+    /// to the angle `end` (both in radians, clockwise). The path must already
+    /// be at the start point.
+    fn ellipse_arc(&mut self, center: Point, radius: Size, start: f64, end: f64) {
+        // Split the arc into pieces of at most a quarter turn so that each
+        // can be approximated well by a single bezier curve.
+        let n = ((end - start).abs() / FRAC_PI_2).ceil().max(1.0);
+        let delta = (end - start) / n;
+        let k = 4.0 / 3.0 * (delta / 4.0).tan();
+        let tangent = |t: f64| Point::new(-radius.x * t.sin(), radius.y * t.cos());
+        for i in 0..n as usize {
+            let a = start + i as f64 * delta;
+            let b = a + delta;
+            let (p, q) = (
+                ellipse_arc_point(center, radius, a),
+                ellipse_arc_point(center, radius, b),
+            );
+            self.cubic_to(p + tangent(a) * k, q - tangent(b) * k, q);
+        }
+    }
+}
+
+/// The point on the axis-aligned ellipse around `center` at the given angle (in
+/// radians, clockwise from the positive x-axis).
+pub(crate) fn ellipse_arc_point(center: Point, radius: Size, angle: f64) -> Point {
+    center + Point::new(radius.x * angle.cos(), radius.y * angle.sin())
 }
 
-/// Get the control points for a bezier curve that approximates a circular arc for
-/// a start point, an end point and a center of the circle whose arc connects
-/// the two.
-fn bezier_arc_control(start: Point, center: Point, end: Point) -> [Point; 2] {
+/// Get the control points for a bezier curve that approximates an elliptical
+/// arc for a start point, an end point and the center and radii of the
+/// axis-aligned ellipse whose arc connects the two.
+fn bezier_arc_control(
+    start: Point,
+    center: Point,
+    radius: Size,
+    end: Point,
+) -> [Point; 2] {
+    // Compute the control points for the corresponding circular arc on the
+    // unit circle and then scale them back onto the ellipse.
     // https://stackoverflow.com/a/44829356/1567835
     let a = start - center;
     let b = end - center;
+    let (ax, ay) = (a.x / radius.x, a.y / radius.y);
+    let (bx, by) = (b.x / radius.x, b.y / radius.y);
 
-    let q1 = a.x.to_raw() * a.x.to_raw() + a.y.to_raw() * a.y.to_raw();
-    let q2 = q1 + a.x.to_raw() * b.x.to_raw() + a.y.to_raw() * b.y.to_raw();
-    let k2 = (4.0 / 3.0) * ((2.0 * q1 * q2).sqrt() - q2)
-        / (a.x.to_raw() * b.y.to_raw() - a.y.to_raw() * b.x.to_raw());
+    let q1 = ax * ax + ay * ay;
+    let q2 = q1 + ax * bx + ay * by;
+    let k2 = (4.0 / 3.0) * ((2.0 * q1 * q2).sqrt() - q2) / (ax * by - ay * bx);
 
-    let control_1 = Point::new(center.x + a.x - k2 * a.y, center.y + a.y + k2 * a.x);
-    let control_2 = Point::new(center.x + b.x + k2 * b.y, center.y + b.y - k2 * b.x);
+    let control_1 = Point::new(
+        center.x + (ax - k2 * ay) * radius.x,
+        center.y + (ay + k2 * ax) * radius.y,
+    );
+    let control_2 = Point::new(
+        center.x + (bx + k2 * by) * radius.x,
+        center.y + (by - k2 * bx) * radius.y,
+    );
 
     [control_1, control_2]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::{FromValue, IntoValue, Value};
+    use crate::visualize::{DashPattern, Rgb, StrokeAlign};
+
+    fn rect(radius: Abs, stroke: Option<Abs>) -> Shape {
+        let stroke =
+            stroke.map(|thickness| FixedStroke { thickness, ..Default::default() });
+        let mut shapes = styled_rect(
+            Size::splat(Abs::pt(100.0)),
+            Corners::splat(Radius::circular(radius.into())),
+            Some(Color::BLACK.into()),
+            Sides::splat(stroke),
+        );
+        shapes.remove(0)
+    }
+
+    #[test]
+    fn test_shape_contains_rect() {
+        let shape = rect(Abs::zero(), None);
+        assert!(shape.contains(Point::splat(Abs::pt(50.0))));
+        assert!(shape.contains(Point::zero()));
+        assert!(shape.contains(Point::new(Abs::pt(100.0), Abs::pt(30.0))));
+        assert!(!shape.contains(Point::new(Abs::pt(100.5), Abs::pt(30.0))));
+        assert!(!shape.contains(Point::splat(Abs::pt(-1.0))));
+    }
+
+    #[test]
+    fn test_shape_contains_rounded_rect() {
+        let shape = rect(Abs::pt(20.0), None);
+        assert!(shape.contains(Point::new(Abs::pt(20.0), Abs::pt(1.0))));
+        assert!(shape.contains(Point::splat(Abs::pt(7.0))));
+        assert!(!shape.contains(Point::splat(Abs::pt(5.0))));
+        assert!(!shape.contains(Point::splat(Abs::pt(1.0))));
+    }
+
+    #[test]
+    fn test_shape_contains_stroke() {
+        let stroke = FixedStroke { thickness: Abs::pt(4.0), ..Default::default() };
+        let shape = Geometry::Rect(Size::splat(Abs::pt(10.0))).stroked(stroke.clone());
+        assert!(shape.contains(Point::new(Abs::pt(-1.5), Abs::pt(5.0))));
+        assert!(shape.contains(Point::new(Abs::pt(-2.0), Abs::pt(5.0))));
+        assert!(!shape.contains(Point::new(Abs::pt(-2.5), Abs::pt(5.0))));
+
+        let line = Geometry::Line(Point::with_x(Abs::pt(10.0))).stroked(stroke);
+        assert!(line.contains(Point::new(Abs::pt(5.0), Abs::pt(1.0))));
+        assert!(!line.contains(Point::new(Abs::pt(5.0), Abs::pt(3.0))));
+        assert!(!line.contains(Point::new(Abs::pt(13.0), Abs::zero())));
+    }
+
+    #[test]
+    fn test_link_strips_fit_into_outline() {
+        let size = Size::new(Abs::pt(80.0), Abs::pt(80.0));
+        let outline = ellipse(size, None, None);
+        let strips = link_strips(&outline, size);
+        assert!(strips.len() >= LINK_STRIPS - 2 && strips.len() <= LINK_STRIPS);
+        for (pos, size) in strips {
+            let corners = [
+                pos,
+                pos + Point::with_x(size.x),
+                pos + size.to_point(),
+                pos + Point::with_y(size.y),
+            ];
+            assert!(corners.into_iter().all(|corner| outline.contains(corner)));
+        }
+
+        let rect = Geometry::Rect(size).filled(Color::BLACK.into());
+        assert_eq!(link_strips(&rect, size), vec![(Point::zero(), size)]);
+    }
+
+    #[test]
+    fn test_link_strips_merge_and_scale() {
+        // Small shapes use fewer strips.
+        let small = Size::splat(Abs::pt(10.0));
+        assert!(link_strips(&ellipse(small, None, None), small).len() <= 2);
+
+        // The straight middle of a rounded rectangle is covered by one strip.
+        let size = Size::splat(Abs::pt(100.0));
+        let outline = rect(Abs::pt(10.0), None);
+        let strips = link_strips(&outline, size);
+        assert!(strips.len() <= 6);
+        assert!(strips.iter().any(|(_, strip)| strip.y >= Abs::pt(60.0)));
+    }
+
+    #[test]
+    fn test_inside_stroke_stays_within_frame() {
+        let frame = Size::new(Abs::pt(40.0), Abs::pt(30.0));
+        let thickness = Abs::pt(6.0);
+        let stroke = |align| {
+            Smart::Custom(Sides::splat(Some(Stroke {
+                thickness: Smart::Custom(thickness),
+                align: Smart::Custom(align),
+                ..Default::default()
+            })))
+        };
+
+        // The stroke extends half its thickness beyond both sides of the
+        // outline.
+        let painted = |align| {
+            let outset = aligned_outset(Sides::splat(Rel::zero()), &stroke(align));
+            let (pos, size) = outset_area(frame, outset);
+            let half = Point::splat(thickness / 2.0);
+            (pos - half, pos + size.to_point() + half)
+        };
+
+        assert_eq!(painted(StrokeAlign::Inside), (Point::zero(), frame.to_point()));
+        let (min, max) = painted(StrokeAlign::Center);
+        assert!(min.x < Abs::zero() && max.x > frame.x);
+        let (min, max) = painted(StrokeAlign::Outside);
+        assert_eq!(min, Point::splat(-thickness));
+        assert_eq!(max, frame.to_point() + Point::splat(thickness));
+    }
+
+    #[test]
+    fn test_resolve_radius_scales_proportionally() {
+        let size = Size::new(Abs::pt(60.0), Abs::pt(30.0));
+        let pt = |v| Radius::circular(Rel::from(Abs::pt(v)));
+        let zero = Radius::default();
+        let resolve = |radius| resolve_radius(size, radius, Sides::splat(Abs::zero()));
+
+        // A single corner may take up a whole side.
+        let single = Corners { top_left: pt(30.0), ..Corners::splat(zero) };
+        assert_eq!(resolve(single).top_left, Radius::circular(Abs::pt(30.0)));
+
+        // Radii that overlap along the left side are all scaled by the factor
+        // that makes them fit, including those on other sides.
+        let radii = resolve(Corners::new(pt(40.0), pt(10.0), pt(5.0), pt(20.0)));
+        assert_eq!(
+            radii,
+            Corners::new(
+                Radius::circular(Abs::pt(20.0)),
+                Radius::circular(Abs::pt(5.0)),
+                Radius::circular(Abs::pt(2.5)),
+                Radius::circular(Abs::pt(10.0)),
+            )
+        );
+
+        // Elliptical radii are scaled by the same factor on both axes.
+        let radius = Radius::new(Rel::one(), Ratio::new(0.8).into());
+        let elliptical = Corners {
+            top_left: radius,
+            top_right: radius,
+            ..Corners::splat(zero)
+        };
+        assert_eq!(
+            resolve(elliptical).top_left,
+            Radius::new(Abs::pt(30.0), Abs::pt(12.0))
+        );
+    }
+
+    #[test]
+    fn test_soft_items_skip_layers_shrunk_beyond_zero() {
+        let size = Size::splat(Abs::pt(4.0));
+        let layers: Vec<_> = blur_layers(Abs::pt(20.0), 1.0)
+            .into_iter()
+            .map(|(grow, _)| (grow, Color::BLACK.into()))
+            .collect();
+
+        for kind in [ShapeKind::Rect, ShapeKind::Circle] {
+            let radius = Corners::splat(Radius::default());
+            let items = soft_items(
+                kind,
+                (Point::zero(), size),
+                radius,
+                None,
+                0.0,
+                &layers,
+                Span::detached(),
+            );
+            assert!(!items.is_empty() && items.len() < layers.len());
+            for (pos, _) in items {
+                // Each layer is moved up and left by as much as it grows.
+                let grown = size - Size::splat(2.0 * pos.x);
+                assert!(grown.x > Abs::zero() && grown.y > Abs::zero());
+            }
+        }
+    }
+
+    #[test]
+    fn test_fragment_radius_rounds_outer_corners() {
+        let radius = Corners::splat(Abs::pt(5.0));
+        let top = Corners {
+            top_left: Abs::pt(5.0),
+            top_right: Abs::pt(5.0),
+            ..Corners::splat(Abs::zero())
+        };
+        let bottom = Corners {
+            bottom_left: Abs::pt(5.0),
+            bottom_right: Abs::pt(5.0),
+            ..Corners::splat(Abs::zero())
+        };
+        assert_eq!(fragment_radius(radius, 0, 1), radius);
+        assert_eq!(fragment_radius(radius, 0, 3), top);
+        assert_eq!(fragment_radius(radius, 1, 3), Corners::splat(Abs::zero()));
+        assert_eq!(fragment_radius(radius, 2, 3), bottom);
+    }
+
+    #[test]
+    fn test_scalar_radius_applies_to_all_corners() {
+        let radius = Radius::circular(Rel::from(Length::from(Abs::pt(6.0))));
+        let corners =
+            Corners::<Option<Radius<Rel<Length>>>>::from_value(Abs::pt(6.0).into_value())
+                .unwrap();
+        assert_eq!(corners, Corners::splat(Some(radius)));
+
+        let outer = Corners::splat(Radius::default());
+        assert_eq!(corners.fold(outer), Corners::splat(radius));
+    }
+
+    #[test]
+    fn test_none_stroke_overrides_inherited_stroke() {
+        let none = Smart::<Sides<Option<Option<Stroke>>>>::from_value(Value::None);
+        assert_eq!(none, Ok(Smart::Custom(Sides::splat(Some(None)))));
+
+        type Strokes = Smart<Sides<Option<Option<Stroke<Abs>>>>>;
+        let none: Strokes = Smart::Custom(Sides::splat(Some(None)));
+
+        let outer = Smart::Custom(Sides::splat(Some(Stroke {
+            thickness: Smart::Custom(Abs::pt(2.0)),
+            ..Default::default()
+        })));
+        assert_eq!(none.fold(outer.clone()), Smart::Custom(Sides::splat(None)));
+        assert_eq!(Strokes::Auto.fold(outer), Smart::Auto);
+    }
+
+    #[test]
+    fn test_ring_leaves_hole_empty() {
+        let size = Size::splat(Abs::pt(100.0));
+        let ring =
+            Geometry::Path(ring_path(size, Abs::pt(30.0))).filled(Color::BLACK.into());
+        assert!(ring.contains(Point::new(Abs::pt(10.0), Abs::pt(50.0))));
+        assert!(ring.contains(Point::new(Abs::pt(50.0), Abs::pt(90.0))));
+        assert!(!ring.contains(Point::splat(Abs::pt(50.0))));
+        assert!(!ring.contains(Point::new(Abs::pt(75.0), Abs::pt(50.0))));
+        assert!(!ring.contains(Point::splat(Abs::pt(2.0))));
+    }
+
+    #[test]
+    fn test_dashed_rounded_rect_is_one_closed_path() {
+        let stroke = FixedStroke {
+            dash_pattern: Some(DashPattern {
+                array: vec![Abs::pt(2.0)],
+                phase: Abs::zero(),
+            }),
+            ..Default::default()
+        };
+        let shapes = styled_rect(
+            Size::new(Abs::pt(50.0), Abs::pt(30.0)),
+            Corners::splat(Radius::circular(Abs::pt(10.0).into())),
+            None,
+            Sides::splat(Some(stroke)),
+        );
+        assert_eq!(shapes.len(), 1);
+        let Geometry::Path(path) = &shapes[0].geometry else { panic!() };
+        assert_eq!(
+            path.0
+                .iter()
+                .filter(|item| matches!(item, PathItem::MoveTo(_)))
+                .count(),
+            1
+        );
+        assert_eq!(path.0.last(), Some(&PathItem::ClosePath));
+    }
+
+    #[test]
+    fn test_relative_radius_is_resolved_in_geometry() {
+        let size = Size::new(Abs::pt(100.0), Abs::pt(40.0));
+        let styled = |radius: Rel<Abs>| {
+            let radius = Corners::splat(Radius::circular(radius));
+            styled_rect(size, radius, Some(Color::BLACK.into()), Sides::splat(None))
+        };
+
+        // A circular radius is relative to the shorter side.
+        let relative = styled(Ratio::new(0.5).into());
+        assert_eq!(relative, styled(Abs::pt(20.0).into()));
+        assert!(matches!(relative[0].geometry, Geometry::Path(_)));
+        assert_eq!(relative[0].geometry.bbox_size(), size);
+    }
+
+    #[test]
+    fn test_shape_contains_ellipse() {
+        let shape = ellipse(Size::new(Abs::pt(100.0), Abs::pt(50.0)), None, None);
+        assert!(shape.contains(Point::new(Abs::pt(50.0), Abs::pt(25.0))));
+        assert!(shape.contains(Point::new(Abs::pt(1.0), Abs::pt(25.0))));
+        assert!(shape.contains(Point::new(Abs::pt(50.0), Abs::pt(0.0))));
+        assert!(!shape.contains(Point::new(Abs::pt(10.0), Abs::pt(5.0))));
+        assert!(!shape.contains(Point::new(Abs::pt(90.0), Abs::pt(45.0))));
+    }
+
+    #[test]
+    fn test_polygon_bbox_and_contains() {
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let shape = Geometry::Polygon(vec![pt(10.0, 0.0), pt(30.0, 20.0), pt(0.0, 40.0)])
+            .filled(Color::BLACK.into());
+        assert_eq!(shape.geometry.bbox_size(), Size::new(Abs::pt(30.0), Abs::pt(40.0)));
+        assert!(shape.contains(pt(12.0, 20.0)));
+        assert!(!shape.contains(pt(2.0, 2.0)));
+        assert_eq!(Geometry::Polygon(vec![]).bbox_size(), Size::zero());
+    }
+
+    #[test]
+    fn test_even_odd_excludes_nested_region() {
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let mut star = Geometry::Polygon(vec![
+            pt(20.0, 0.0),
+            pt(32.0, 36.0),
+            pt(0.0, 14.0),
+            pt(40.0, 14.0),
+            pt(8.0, 36.0),
+        ])
+        .filled(Color::BLACK.into());
+        let center = pt(20.0, 20.0);
+        assert!(star.contains(center));
+        star.fill_rule = FillRule::EvenOdd;
+        assert!(!star.contains(center));
+        assert!(star.contains(pt(20.0, 5.0)));
+    }
+
+    #[test]
+    fn test_asymmetric_outset_area() {
+        let size = Size::new(Abs::pt(30.0), Abs::pt(20.0));
+        let outset = Sides::new(
+            Abs::zero().into(),
+            Abs::pt(2.0).into(),
+            Abs::pt(20.0).into(),
+            Ratio::new(0.5).into(),
+        );
+
+        // The area starts at the left edge and only grows to the right.
+        let (pos, area) = outset_area(size, outset);
+        assert_eq!(pos, Point::new(Abs::zero(), Abs::pt(-2.0)));
+        assert_eq!(area, Size::new(Abs::pt(50.0), Abs::pt(32.0)));
+    }
+
+    fn assert_bbox(geometry: &Geometry, min: (f64, f64), size: (f64, f64)) {
+        let (pos, extent) = geometry.bbox();
+        let approx = |a: Abs, b: f64| (a.to_pt() - b).abs() < 1e-6;
+        assert!(approx(pos.x, min.0) && approx(pos.y, min.1), "{pos:?}");
+        assert!(approx(extent.x, size.0) && approx(extent.y, size.1), "{extent:?}");
+        assert_eq!(geometry.bbox_size(), extent);
+    }
+
+    #[test]
+    fn test_equal_elliptical_radius_stays_elliptical() {
+        let size = Size::new(Abs::pt(60.0), Abs::pt(40.0));
+        let half = Rel::from(Ratio::new(0.5));
+        assert_eq!(
+            Radius::new(half, half).relative_to(size),
+            Radius::new(Abs::pt(30.0), Abs::pt(20.0)),
+        );
+        assert_eq!(
+            Radius::circular(half).relative_to(size),
+            Radius::circular(Abs::pt(20.0)),
+        );
+    }
+
+    #[test]
+    fn test_rounded_radius_is_an_eighth_of_the_shorter_side() {
+        let size = Size::new(Abs::pt(80.0), Abs::pt(40.0));
+        let radius = rounded(Corners::splat(Radius::default()), true);
+        assert_eq!(
+            radius.relative_to(size),
+            Corners::splat(Radius::circular(Abs::pt(5.0)))
+        );
+
+        // Corners that already have a radius keep it.
+        let given = Radius::circular(Rel::from(Abs::pt(2.0)));
+        let radius = rounded(Corners::splat(given), true);
+        assert_eq!(
+            radius.relative_to(size),
+            Corners::splat(Radius::circular(Abs::pt(2.0)))
+        );
+    }
+
+    #[test]
+    fn test_bbox_line() {
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        assert_bbox(&Geometry::Line(pt(-30.0, -40.0)), (-30.0, -40.0), (30.0, 40.0));
+    }
+
+    #[test]
+    fn test_bbox_rect() {
+        assert_bbox(&rect(Abs::zero(), None).geometry, (0.0, 0.0), (100.0, 100.0));
+        assert_bbox(&rect(Abs::pt(20.0), None).geometry, (0.0, 0.0), (100.0, 100.0));
+    }
+
+    #[test]
+    fn test_bbox_ellipse() {
+        let path = ellipse_path(Size::new(Abs::pt(60.0), Abs::pt(20.0)));
+        assert_bbox(&Geometry::Path(path), (0.0, 0.0), (60.0, 20.0));
+    }
+
+    #[test]
+    fn test_bbox_polygon() {
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let polygon =
+            Geometry::Polygon(vec![pt(20.0, 5.0), pt(35.0, 30.0), pt(5.0, 25.0)]);
+        assert_bbox(&polygon, (5.0, 5.0), (30.0, 25.0));
+        assert_bbox(&Geometry::Polygon(vec![]), (0.0, 0.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_bbox_path() {
+        let pt = |x, y| Point::new(Abs::pt(x), Abs::pt(y));
+        let mut path = Path::new();
+        path.move_to(pt(10.0, 10.0));
+        path.line_to(pt(30.0, 10.0));
+        path.line_to(pt(20.0, 40.0));
+        path.close_path();
+        assert_bbox(&Geometry::Path(path), (10.0, 10.0), (20.0, 30.0));
+        assert_bbox(&Geometry::Path(Path::new()), (0.0, 0.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_bbox_arc() {
+        // A quarter arc from three to six o'clock around (50pt, 50pt).
+        let center = Point::splat(Abs::pt(50.0));
+        let radius = Size::splat(Abs::pt(20.0));
+        let mut path = Path::new();
+        path.move_to(ellipse_arc_point(center, radius, 0.0));
+        path.ellipse_arc(center, radius, 0.0, FRAC_PI_2);
+        assert_bbox(&Geometry::Path(path), (50.0, 50.0), (20.0, 20.0));
+    }
+
+    #[test]
+    fn test_equal_shapes_hash_equally() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |shape: &Shape| {
+            let mut state = DefaultHasher::new();
+            shape.hash(&mut state);
+            state.finish()
+        };
+
+        // The same red, once from bytes and once from floats, and a zero that
+        // is negative in one of the shapes.
+        let shape = |color: Color, zero: f64| {
+            Geometry::Rect(Size::new(Abs::pt(zero), Abs::pt(10.0))).filled(color.into())
+        };
+        let a = shape(Color::from_u8(255, 0, 0, 255), 0.0);
+        let b = shape(Color::Rgb(Rgb::new(1.0, 0.0001, 0.0, 1.0)), -0.0);
+        let c = shape(Color::BLUE, 0.0);
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_square_relayout() {
+        let size = |x, y| Size::new(Abs::pt(x), Abs::pt(y));
+        let region = size(100.0, 100.0);
+        let (min, max) = (Size::zero(), Size::splat(Abs::inf()));
+
+        // A first layout that is already square is kept.
+        assert_eq!(square_relayout(size(20.0, 20.0), region, min, max), None);
+        assert_eq!(square_relayout(Size::zero(), region, min, max), Some(Abs::zero()));
+
+        // Otherwise, the longer side is laid out again, within the region and
+        // the limits.
+        let relayout = |s, min, max| square_relayout(s, region, min, max);
+        assert_eq!(relayout(size(30.0, 20.0), min, max), Some(Abs::pt(30.0)));
+        assert_eq!(relayout(size(20.0, 150.0), min, max), Some(Abs::pt(100.0)));
+        assert_eq!(
+            relayout(size(20.0, 20.0), size(25.0, 25.0), max),
+            Some(Abs::pt(25.0))
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::diag::{bail, SourceResult, StrResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    cast, dict, elem, Cast, Dict, FromValue, NativeElement, Resolve, Smart, StyleChain,
+};
+use crate::layout::{Fragment, Frame, FrameItem, Layout, Length, Point, Regions, Size};
+use crate::util::Numeric;
+use crate::visualize::{ellipse, FillRule, FixedStroke, Geometry, Paint, Shape, Stroke};
+
+/// Many simple shapes, drawn at once.
+///
+/// This draws rectangles and ellipses at explicit positions into a single
+/// frame. In contrast to placing many individual [rectangles]($rect) or
+/// [ellipses]($ellipse), the shapes are not laid out one by one, which makes
+/// this much faster for things like the points of a scatter plot. In turn,
+/// the shapes cannot have content, rounded corners, or any of the other
+/// features of the individual shape functions.
+///
+/// Each shape is a dictionary with the following keys:
+///
+/// - `x` and `y`: The position of the shape's top left corner, relative to
+///   the top left corner of the whole element. Default to `{0pt}`.
+/// - `width` and `height`: The shape's size. Both are required.
+/// - `kind`: Either `{"rect"}` or `{"ellipse"}`. Defaults to `{"rect"}`.
+/// - `fill` and `stroke`: Override the [`fill`]($shapes.fill) and
+///   [`stroke`]($shapes.stroke) of the whole element for this shape.
+///
+/// The element is as large as needed to contain all shapes, measured from its
+/// top left corner.
+///
+/// # Example
+/// ```example
+/// #let points = range(12).map(i => (i * 6pt, calc.rem(i * 7, 5) * 6pt))
+/// #shapes(
+///   fill: blue,
+///   ..points.map(((x, y)) => (
+///     x: x, y: y, width: 4pt, height: 4pt, kind: "ellipse",
+///   )),
+///   (x: 0pt, y: 30pt, width: 70pt, height: 0.5pt, fill: gray),
+/// )
+/// ```
+#[elem(Layout)]
+pub struct ShapesElem {
+    /// How to fill the shapes.
+    ///
+    /// When setting a fill, the default stroke disappears. To create shapes
+    /// with both fill and stroke, you have to configure both.
+    pub fill: Option<Paint>,
+
+    /// How to [stroke]($stroke) the shapes.
+    ///
+    /// Can be set to `{none}` to disable the stroke or to `{auto}` for a
+    /// stroke of `{1pt}` black if and if only if a shape has no fill.
+    #[resolve]
+    #[fold]
+    pub stroke: Smart<Option<Stroke>>,
+
+    /// The shapes to draw, from bottom to top.
+    #[variadic]
+    pub children: Vec<ShapeMark>,
+}
+
+impl Layout for ShapesElem {
+    #[tracing::instrument(name = "ShapesElem::layout", skip_all)]
+    fn layout(
+        &self,
+        _: &mut Engine,
+        styles: StyleChain,
+        _: Regions,
+    ) -> SourceResult<Fragment> {
+        let fill = self.fill(styles);
+        let stroke = self.stroke(styles);
+        if let Smart::Custom(Some(stroke)) = &stroke {
+            stroke.ensure_absolute(self.span())?;
+        }
+
+        let children = self.children();
+        let mut items = Vec::with_capacity(children.len());
+        let mut size = Size::zero();
+        for mark in children {
+            let pos = Point::new(mark.x.resolve(styles), mark.y.resolve(styles));
+            let extent =
+                Size::new(mark.width.resolve(styles), mark.height.resolve(styles));
+            size = size.max((pos + extent.to_point()).to_size());
+
+            // A shape's own fill and stroke take precedence.
+            let fill = mark.fill.clone().unwrap_or_else(|| fill.clone());
+            let stroke = match &mark.stroke {
+                Some(stroke) => Smart::Custom(stroke.clone().map(|s| s.resolve(styles))),
+                None => stroke.clone(),
+            };
+            if let Smart::Custom(Some(stroke)) = &stroke {
+                stroke.ensure_absolute(self.span())?;
+            }
+            let stroke = match stroke {
+                Smart::Auto if fill.is_none() => Some(FixedStroke::default()),
+                Smart::Auto => None,
+                Smart::Custom(stroke) => stroke.map(Stroke::unwrap_or_default),
+            };
+
+            let shape = match mark.kind {
+                MarkKind::Rect => Shape {
+                    geometry: Geometry::Rect(extent),
+                    fill,
+                    stroke,
+                    fill_rule: FillRule::default(),
+                },
+                MarkKind::Ellipse => ellipse(extent, fill, stroke),
+            };
+            items.push((pos, FrameItem::Shape(shape, self.span())));
+        }
+
+        if !size.is_finite() {
+            bail!(self.span(), "cannot create shapes with infinite size");
+        }
+
+        let mut frame = Frame::hard(size);
+        frame.prepend_multiple(items);
+        Ok(Fragment::frame(frame))
+    }
+}
+
+/// A single shape drawn by the [`shapes`]($shapes) element.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct ShapeMark {
+    /// The shape's outline.
+    pub kind: MarkKind,
+    /// The horizontal position of the shape's top left corner.
+    pub x: Length,
+    /// The vertical position of the shape's top left corner.
+    pub y: Length,
+    /// The shape's width.
+    pub width: Length,
+    /// The shape's height.
+    pub height: Length,
+    /// A fill that overrides the element's fill.
+    pub fill: Option<Option<Paint>>,
+    /// A stroke that overrides the element's stroke.
+    pub stroke: Option<Option<Stroke>>,
+}
+
+/// The outline of a shape drawn by the [`shapes`]($shapes) element.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum MarkKind {
+    /// A rectangle.
+    Rect,
+    /// An ellipse.
+    Ellipse,
+}
+
+cast! {
+    ShapeMark,
+    self => {
+        let mut dict = dict! {
+            "kind" => self.kind,
+            "x" => self.x,
+            "y" => self.y,
+            "width" => self.width,
+            "height" => self.height,
+        };
+        if let Some(fill) = self.fill {
+            dict.insert("fill".into(), fill.into_value());
+        }
+        if let Some(stroke) = self.stroke {
+            dict.insert("stroke".into(), stroke.into_value());
+        }
+        dict.into_value()
+    },
+    mut dict: Dict => {
+        let Some(width) = take(&mut dict, "width")? else {
+            bail!("shape is missing a width")
+        };
+        let Some(height) = take(&mut dict, "height")? else {
+            bail!("shape is missing a height")
+        };
+        let mark = ShapeMark {
+            kind: take(&mut dict, "kind")?.unwrap_or(MarkKind::Rect),
+            x: take(&mut dict, "x")?.unwrap_or_default(),
+            y: take(&mut dict, "y")?.unwrap_or_default(),
+            width,
+            height,
+            fill: take(&mut dict, "fill")?,
+            stroke: take(&mut dict, "stroke")?,
+        };
+        dict.finish(&["kind", "x", "y", "width", "height", "fill", "stroke"])?;
+        mark
+    },
+}
+
+/// Take and cast an optional entry from a dictionary.
+fn take<T: FromValue>(dict: &mut Dict, key: &str) -> StrResult<Option<T>> {
+    dict.take(key).ok().map(T::from_value).transpose()
+}
@@ -1,19 +1,20 @@
 use ecow::EcoString;
 
-use crate::diag::{SourceResult, StrResult};
+use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::foundations::{
-    cast, dict, func, scope, ty, Args, Cast, Dict, Fold, FromValue, NoneValue, Repr,
-    Resolve, Smart, StyleChain, Value,
+    array, cast, dict, func, scope, ty, Args, Array, Cast, Dict, Fold, FromValue,
+    NoneValue, Repr, Resolve, Smart, StyleChain, Value,
 };
-use crate::layout::{Abs, Length};
+use crate::layout::{Abs, Length, Ratio, Rel};
+use crate::syntax::{Span, Spanned};
 use crate::util::{Numeric, Scalar};
 use crate::visualize::{Color, Gradient, Paint, Pattern};
 
 /// Defines how to draw a line.
 ///
 /// A stroke has a _paint_ (a solid color or gradient), a _thickness,_ a line
-/// _cap,_ a line _join,_ a _miter limit,_ and a _dash_ pattern. All of these
-/// values are optional and have sensible defaults.
+/// _cap,_ a line _join,_ a _miter limit,_ a _dash_ pattern, and an
+/// _alignment._ All of these values are optional and have sensible defaults.
 ///
 /// # Example
 /// ```example
@@ -56,6 +57,9 @@ pub struct Stroke<T: Numeric = Length> {
     pub paint: Smart<Paint>,
     /// The stroke's thickness.
     pub thickness: Smart<T>,
+    /// The part of the stroke's thickness that is relative to the size of the
+    /// stroked shape. It belongs to `thickness` and is only set along with it.
+    pub relative_thickness: Ratio,
     /// The stroke's line cap.
     pub line_cap: Smart<LineCap>,
     /// The stroke's line join.
@@ -64,6 +68,10 @@ pub struct Stroke<T: Numeric = Length> {
     pub dash_pattern: Smart<Option<DashPattern<T>>>,
     /// The miter limit.
     pub miter_limit: Smart<Scalar>,
+    /// Where the stroke is placed relative to a shape's outline.
+    pub align: Smart<StrokeAlign>,
+    /// Whether the stroke is drawn exactly one device pixel wide.
+    pub hairline: Smart<bool>,
 }
 
 #[scope]
@@ -100,8 +108,19 @@ impl Stroke {
         /// The stroke's thickness.
         ///
         /// If set to `{auto}`, the value is inherited, defaulting to `{1pt}`.
+        ///
+        /// On a [rectangle]($rect), [square]($square), [ellipse]($ellipse), or
+        /// [circle]($circle), the thickness can be relative to the shape's
+        /// shorter side. Other elements fail with an error for a relative
+        /// thickness.
+        ///
+        /// ```example
+        /// #set rect(stroke: (thickness: 5%))
+        /// #rect(width: 20pt, height: 20pt)
+        /// #rect(width: 60pt, height: 60pt)
+        /// ```
         #[external]
-        thickness: Smart<Length>,
+        thickness: Smart<Rel<Length>>,
 
         /// How the ends of the stroke are rendered.
         ///
@@ -128,11 +147,17 @@ impl Stroke {
         ///   - `{"dash-dotted"}`
         ///   - `{"densely-dash-dotted"}`
         ///   - `{"loosely-dash-dotted"}`
+        ///
+        ///   The dashes and gaps of these patterns are multiples of the line
+        ///   thickness, so they scale with it.
         /// - An [array]($array) with alternating lengths for dashes and gaps. You can
-        ///   also use the string `{"dot"}` for a length equal to the line thickness.
+        ///   also use the string `{"dot"}` for a length equal to the line thickness
+        ///   or a number for a multiple of it.
         /// - A [dictionary]($dictionary) with the keys `array` (same as the array
         ///   above), and `phase` (of type [length]($length)), which defines where in
-        ///   the pattern to start drawing.
+        ///   the pattern to start drawing. To make the dashes of a stroke continue
+        ///   those of an adjacent stroke, set its phase to the preceding stroke's
+        ///   length.
         ///
         /// If set to `{auto}`, the value is inherited, defaulting to `{none}`.
         ///
@@ -154,7 +179,8 @@ impl Stroke {
         /// `{"miter"}`.
         ///
         /// Specifically, the miter limit is the maximum ratio between the
-        /// corner's protrusion length and the stroke's thickness.
+        /// corner's protrusion length and the stroke's thickness. It must be at
+        /// least `{1.0}`.
         ///
         /// If set to `{auto}`, the value is inherited, defaulting to `{4.0}`.
         ///
@@ -171,6 +197,46 @@ impl Stroke {
         /// ```
         #[external]
         miter_limit: Smart<f64>,
+
+        /// Where to place the stroke relative to the outline of a rectangle,
+        /// square, ellipse, or circle. Other shapes and lines always center
+        /// their stroke on the outline.
+        ///
+        /// If set to `{auto}`, the value is inherited, defaulting to
+        /// `{"center"}`.
+        ///
+        /// ```example
+        /// #set rect(width: 30pt, height: 30pt, fill: aqua)
+        /// #stack(
+        ///   dir: ltr,
+        ///   spacing: 1em,
+        ///   rect(stroke: (thickness: 6pt, align: "inside")),
+        ///   rect(stroke: (thickness: 6pt, align: "center")),
+        ///   rect(stroke: (thickness: 6pt, align: "outside")),
+        /// )
+        /// ```
+        #[external]
+        align: Smart<StrokeAlign>,
+
+        /// Whether to draw the stroke exactly one device pixel wide, so that
+        /// very thin strokes like fine table rules don't disappear at low
+        /// resolutions.
+        ///
+        /// A hairline is one pixel wide at any zoom level and in all export
+        /// formats, regardless of its thickness. It is thus meant for strokes
+        /// that are thinner than a pixel. The thickness still determines how
+        /// far an [aligned]($stroke.align) stroke is moved.
+        ///
+        /// If set to `{auto}`, the value is inherited, defaulting to
+        /// `{false}`.
+        ///
+        /// ```example
+        /// #set line(length: 100%)
+        /// #line(stroke: 0.05pt)
+        /// #line(stroke: (thickness: 0.05pt, hairline: true))
+        /// ```
+        #[external]
+        hairline: Smart<bool>,
     ) -> SourceResult<Stroke> {
         if let Some(stroke) = args.eat::<Stroke>()? {
             return Ok(stroke);
@@ -181,19 +247,28 @@ impl Stroke {
         }
 
         let paint = take::<Paint>(args, "paint")?;
-        let thickness = take::<Length>(args, "thickness")?;
+        let (thickness, relative_thickness) =
+            split_thickness(take::<Rel<Length>>(args, "thickness")?);
         let line_cap = take::<LineCap>(args, "cap")?;
         let line_join = take::<LineJoin>(args, "join")?;
         let dash_pattern = take::<Option<DashPattern>>(args, "dash")?;
-        let miter_limit = take::<f64>(args, "miter-limit")?.map(Scalar::new);
+        let miter_limit = match args.named::<Spanned<Smart<f64>>>("miter-limit")? {
+            Some(Spanned { v, span }) => check_miter_limit(v).at(span)?,
+            None => Smart::Auto,
+        };
+        let align = take::<StrokeAlign>(args, "align")?;
+        let hairline = take::<bool>(args, "hairline")?;
 
         Ok(Self {
             paint,
             thickness,
+            relative_thickness,
             line_cap,
             line_join,
             dash_pattern,
             miter_limit,
+            align,
+            hairline,
         })
     }
 }
@@ -207,6 +282,7 @@ impl<T: Numeric> Stroke<T> {
         Stroke {
             paint: self.paint,
             thickness: self.thickness.map(&f),
+            relative_thickness: self.relative_thickness,
             line_cap: self.line_cap,
             line_join: self.line_join,
             dash_pattern: self.dash_pattern.map(|pattern| {
@@ -216,19 +292,57 @@ impl<T: Numeric> Stroke<T> {
                         .into_iter()
                         .map(|l| match l {
                             DashLength::Length(v) => DashLength::Length(f(v)),
-                            DashLength::LineWidth => DashLength::LineWidth,
+                            DashLength::LineWidth(factor) => {
+                                DashLength::LineWidth(factor)
+                            }
                         })
                         .collect(),
                     phase: f(pattern.phase),
                 })
             }),
             miter_limit: self.miter_limit,
+            align: self.align,
+            hairline: self.hairline,
         }
     }
+
+    /// The stroke's thickness, including its relative part.
+    pub fn full_thickness(&self) -> Smart<Rel<T>> {
+        self.thickness
+            .map(|thickness| Rel::new(self.relative_thickness, thickness))
+    }
+
+    /// Fail if part of the thickness is relative. Only rectangles, squares,
+    /// ellipses, and circles resolve relative thicknesses against their size,
+    /// so all other elements with a stroke must reject them.
+    pub fn ensure_absolute(&self, span: Span) -> SourceResult<()> {
+        if !self.relative_thickness.is_zero() {
+            bail!(
+                span, "stroke thickness must be absolute";
+                hint: "only rectangles, squares, ellipses, and circles support relative stroke thicknesses"
+            );
+        }
+        Ok(())
+    }
 }
 
 impl Stroke<Abs> {
+    /// Resolve a relative thickness against the `whole`.
+    pub fn relative_to(self, whole: Abs) -> Self {
+        Self {
+            thickness: self
+                .thickness
+                .map(|thickness| thickness + self.relative_thickness.of(whole)),
+            relative_thickness: Ratio::zero(),
+            ..self
+        }
+    }
+
     /// Unpack the stroke, filling missing fields from the `default`.
+    ///
+    /// A relative thickness must first be resolved with
+    /// [`relative_to`](Self::relative_to) or rejected with
+    /// [`ensure_absolute`](Self::ensure_absolute).
     pub fn unwrap_or(self, default: FixedStroke) -> FixedStroke {
         let thickness = self.thickness.unwrap_or(default.thickness);
         let dash_pattern = self
@@ -252,6 +366,7 @@ impl Stroke<Abs> {
             line_join: self.line_join.unwrap_or(default.line_join),
             dash_pattern,
             miter_limit: self.miter_limit.unwrap_or(default.miter_limit),
+            hairline: self.hairline.unwrap_or(default.hairline),
         }
     }
 
@@ -266,20 +381,25 @@ impl Stroke<Abs> {
 impl<T: Numeric + Repr> Repr for Stroke<T> {
     fn repr(&self) -> EcoString {
         let mut r = EcoString::new();
+        let thickness = self.full_thickness();
         let Self {
             paint,
-            thickness,
             line_cap,
             line_join,
             dash_pattern,
             miter_limit,
+            align,
+            hairline,
+            ..
         } = &self;
         if line_cap.is_auto()
             && line_join.is_auto()
             && dash_pattern.is_auto()
             && miter_limit.is_auto()
+            && align.is_auto()
+            && hairline.is_auto()
         {
-            match (&self.paint, &self.thickness) {
+            match (&self.paint, &thickness) {
                 (Smart::Custom(paint), Smart::Custom(thickness)) => {
                     r.push_str(&thickness.repr());
                     r.push_str(" + ");
@@ -318,7 +438,7 @@ impl<T: Numeric + Repr> Repr for Stroke<T> {
             }
             if let Smart::Custom(dash) = &dash_pattern {
                 r.push_str(sep);
-                r.push_str("cap: ");
+                r.push_str("dash: ");
                 if let Some(dash) = dash {
                     r.push_str(&dash.repr());
                 } else {
@@ -330,6 +450,18 @@ impl<T: Numeric + Repr> Repr for Stroke<T> {
                 r.push_str(sep);
                 r.push_str("miter-limit: ");
                 r.push_str(&miter_limit.get().repr());
+                sep = ", ";
+            }
+            if let Smart::Custom(align) = &align {
+                r.push_str(sep);
+                r.push_str("align: ");
+                r.push_str(&align.repr());
+                sep = ", ";
+            }
+            if let Smart::Custom(hairline) = &hairline {
+                r.push_str(sep);
+                r.push_str("hairline: ");
+                r.push_str(&hairline.repr());
             }
             r.push(')');
         }
@@ -344,10 +476,13 @@ impl Resolve for Stroke {
         Stroke {
             paint: self.paint,
             thickness: self.thickness.resolve(styles),
+            relative_thickness: self.relative_thickness,
             line_cap: self.line_cap,
             line_join: self.line_join,
             dash_pattern: self.dash_pattern.resolve(styles),
             miter_limit: self.miter_limit,
+            align: self.align,
+            hairline: self.hairline,
         }
     }
 }
@@ -356,13 +491,23 @@ impl Fold for Stroke<Abs> {
     type Output = Self;
 
     fn fold(self, outer: Self::Output) -> Self::Output {
+        // The relative part of the thickness is inherited along with it.
+        let relative_thickness = if self.thickness.is_custom() {
+            self.relative_thickness
+        } else {
+            outer.relative_thickness
+        };
+
         Self {
             paint: self.paint.or(outer.paint),
             thickness: self.thickness.or(outer.thickness),
+            relative_thickness,
             line_cap: self.line_cap.or(outer.line_cap),
             line_join: self.line_join.or(outer.line_join),
             dash_pattern: self.dash_pattern.or(outer.dash_pattern),
             miter_limit: self.miter_limit.or(outer.miter_limit),
+            align: self.align.or(outer.align),
+            hairline: self.hairline.or(outer.hairline),
         }
     }
 }
@@ -393,24 +538,52 @@ cast! {
         }
 
         let paint = take::<Paint>(&mut dict, "paint")?;
-        let thickness = take::<Length>(&mut dict, "thickness")?;
+        let (thickness, relative_thickness) =
+            split_thickness(take::<Rel<Length>>(&mut dict, "thickness")?);
         let line_cap = take::<LineCap>(&mut dict, "cap")?;
         let line_join = take::<LineJoin>(&mut dict, "join")?;
         let dash_pattern = take::<Option<DashPattern>>(&mut dict, "dash")?;
-        let miter_limit = take::<f64>(&mut dict, "miter-limit")?;
-        dict.finish(&["paint", "thickness", "cap", "join", "dash", "miter-limit"])?;
+        let miter_limit = check_miter_limit(take::<f64>(&mut dict, "miter-limit")?)?;
+        let align = take::<StrokeAlign>(&mut dict, "align")?;
+        let hairline = take::<bool>(&mut dict, "hairline")?;
+        dict.finish(&[
+            "paint", "thickness", "cap", "join", "dash", "miter-limit", "align", "hairline",
+        ])?;
 
         Self {
             paint,
             thickness,
+            relative_thickness,
             line_cap,
             line_join,
             dash_pattern,
-            miter_limit: miter_limit.map(Scalar::new),
+            miter_limit,
+            align,
+            hairline,
         }
     },
 }
 
+/// Splits a user-provided thickness into its absolute and relative parts.
+fn split_thickness(thickness: Smart<Rel<Length>>) -> (Smart<Length>, Ratio) {
+    let relative = thickness.map_or(Ratio::zero(), |thickness| thickness.rel);
+    (thickness.map(|thickness| thickness.abs), relative)
+}
+
+/// Ensures that a user-provided miter limit is at least one, which is the
+/// smallest ratio between a miter's length and the stroke's thickness.
+fn check_miter_limit(limit: Smart<f64>) -> StrResult<Smart<Scalar>> {
+    if let Smart::Custom(limit) = limit {
+        if !limit.is_finite() {
+            bail!("miter limit must be finite");
+        }
+        if limit < 1.0 {
+            bail!("miter limit must be at least 1.0");
+        }
+    }
+    Ok(limit.map(Scalar::new))
+}
+
 cast! {
     Stroke<Abs>,
     self => self.map(Length::from).into_value(),
@@ -460,6 +633,40 @@ impl Repr for LineJoin {
     }
 }
 
+/// Where a stroke is placed relative to a shape's outline.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum StrokeAlign {
+    /// The stroke is painted entirely inside of the outline.
+    Inside,
+    /// The stroke is centered on the outline.
+    #[default]
+    Center,
+    /// The stroke is painted entirely outside of the outline.
+    Outside,
+}
+
+impl StrokeAlign {
+    /// How far the stroke's center line must be moved outwards from the
+    /// outline for a stroke of the given thickness.
+    pub fn offset(self, thickness: Abs) -> Abs {
+        match self {
+            Self::Inside => -thickness / 2.0,
+            Self::Center => Abs::zero(),
+            Self::Outside => thickness / 2.0,
+        }
+    }
+}
+
+impl Repr for StrokeAlign {
+    fn repr(&self) -> EcoString {
+        match self {
+            Self::Inside => "inside".repr(),
+            Self::Center => "center".repr(),
+            Self::Outside => "outside".repr(),
+        }
+    }
+}
+
 /// A line dash pattern.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct DashPattern<T: Numeric = Length, DT = DashLength<T>> {
@@ -485,6 +692,17 @@ impl<T: Numeric + Repr, DT: Repr> Repr for DashPattern<T, DT> {
     }
 }
 
+impl DashPattern<Abs, Abs> {
+    /// Whether the pattern leaves no gaps, that is, whether it is empty or
+    /// all of its lengths are zero.
+    ///
+    /// Such a pattern draws a solid line. Exporters write it as one, because
+    /// PDF forbids dash arrays of only zeros.
+    pub fn is_solid(&self) -> bool {
+        self.array.iter().all(|length| length.is_zero())
+    }
+}
+
 impl<T: Numeric + Default> From<Vec<DashLength<T>>> for DashPattern<T> {
     fn from(array: Vec<DashLength<T>>) -> Self {
         Self { array, phase: T::default() }
@@ -509,15 +727,15 @@ cast! {
     self => dict! { "array" => self.array, "phase" => self.phase }.into_value(),
 
     "solid" => Vec::new().into(),
-    "dotted" => vec![DashLength::LineWidth, Abs::pt(2.0).into()].into(),
-    "densely-dotted" => vec![DashLength::LineWidth, Abs::pt(1.0).into()].into(),
-    "loosely-dotted" => vec![DashLength::LineWidth, Abs::pt(4.0).into()].into(),
-    "dashed" => vec![Abs::pt(3.0).into(), Abs::pt(3.0).into()].into(),
-    "densely-dashed" => vec![Abs::pt(3.0).into(), Abs::pt(2.0).into()].into(),
-    "loosely-dashed" => vec![Abs::pt(3.0).into(), Abs::pt(6.0).into()].into(),
-    "dash-dotted" => vec![Abs::pt(3.0).into(), Abs::pt(2.0).into(), DashLength::LineWidth, Abs::pt(2.0).into()].into(),
-    "densely-dash-dotted" => vec![Abs::pt(3.0).into(), Abs::pt(1.0).into(), DashLength::LineWidth, Abs::pt(1.0).into()].into(),
-    "loosely-dash-dotted" => vec![Abs::pt(3.0).into(), Abs::pt(4.0).into(), DashLength::LineWidth, Abs::pt(4.0).into()].into(),
+    "dotted" => DashLength::scaled(&[1.0, 2.0]).into(),
+    "densely-dotted" => DashLength::scaled(&[1.0, 1.0]).into(),
+    "loosely-dotted" => DashLength::scaled(&[1.0, 4.0]).into(),
+    "dashed" => DashLength::scaled(&[3.0, 3.0]).into(),
+    "densely-dashed" => DashLength::scaled(&[3.0, 2.0]).into(),
+    "loosely-dashed" => DashLength::scaled(&[3.0, 6.0]).into(),
+    "dash-dotted" => DashLength::scaled(&[3.0, 2.0, 1.0, 2.0]).into(),
+    "densely-dash-dotted" => DashLength::scaled(&[3.0, 1.0, 1.0, 1.0]).into(),
+    "loosely-dash-dotted" => DashLength::scaled(&[3.0, 4.0, 1.0, 4.0]).into(),
 
     array: Vec<DashLength> => Self { array, phase: Length::zero() },
     mut dict: Dict => {
@@ -535,14 +753,24 @@ cast! {
 /// The length of a dash in a line dash pattern.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum DashLength<T: Numeric = Length> {
-    LineWidth,
+    /// A multiple of the line width, which scales with the stroke.
+    LineWidth(Scalar),
+    /// A fixed length.
     Length(T),
 }
 
 impl<T: Numeric> DashLength<T> {
+    /// Dash lengths that are the given multiples of the line width.
+    fn scaled(factors: &[f64]) -> Vec<Self> {
+        factors
+            .iter()
+            .map(|&factor| Self::LineWidth(Scalar::new(factor)))
+            .collect()
+    }
+
     fn finish(self, line_width: T) -> T {
         match self {
-            Self::LineWidth => line_width,
+            Self::LineWidth(factor) => line_width * factor.get(),
             Self::Length(l) => l,
         }
     }
@@ -551,7 +779,8 @@ impl<T: Numeric> DashLength<T> {
 impl<T: Numeric + Repr> Repr for DashLength<T> {
     fn repr(&self) -> EcoString {
         match self {
-            Self::LineWidth => "dot".repr(),
+            Self::LineWidth(factor) if *factor == 1.0 => "dot".repr(),
+            Self::LineWidth(factor) => factor.get().repr(),
             Self::Length(v) => v.repr(),
         }
     }
@@ -562,7 +791,7 @@ impl Resolve for DashLength {
 
     fn resolve(self, styles: StyleChain) -> Self::Output {
         match self {
-            Self::LineWidth => DashLength::LineWidth,
+            Self::LineWidth(factor) => DashLength::LineWidth(factor),
             Self::Length(v) => DashLength::Length(v.resolve(styles)),
         }
     }
@@ -577,13 +806,51 @@ impl From<Abs> for DashLength {
 cast! {
     DashLength,
     self => match self {
-        Self::LineWidth => "dot".into_value(),
+        Self::LineWidth(factor) if factor == 1.0 => "dot".into_value(),
+        Self::LineWidth(factor) => factor.get().into_value(),
         Self::Length(v) => v.into_value(),
     },
-    "dot" => Self::LineWidth,
+    "dot" => Self::LineWidth(Scalar::ONE),
+    v: f64 => Self::LineWidth(Scalar::new(v)),
     v: Length => Self::Length(v),
 }
 
+/// A stroke thickness that changes linearly from the start to the end of a
+/// line or path.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Taper<T: Numeric = Length> {
+    /// The thickness at the start.
+    pub start: T,
+    /// The thickness at the end.
+    pub end: T,
+}
+
+impl Resolve for Taper {
+    type Output = Taper<Abs>;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        Taper {
+            start: self.start.resolve(styles),
+            end: self.end.resolve(styles),
+        }
+    }
+}
+
+cast! {
+    Taper,
+    self => array![self.start, self.end].into_value(),
+    array: Array => {
+        let mut iter = array.into_iter();
+        match (iter.next(), iter.next(), iter.next()) {
+            (Some(start), Some(end), None) => Self {
+                start: start.cast()?,
+                end: end.cast()?,
+            },
+            _ => bail!("taper must contain exactly two lengths"),
+        }
+    },
+}
+
 /// A fully specified stroke of a geometric shape.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct FixedStroke {
@@ -599,6 +866,8 @@ pub struct FixedStroke {
     pub dash_pattern: Option<DashPattern<Abs, Abs>>,
     /// The miter limit. Defaults to 4.0, same as `tiny-skia`.
     pub miter_limit: Scalar,
+    /// Whether the stroke is drawn exactly one device pixel wide.
+    pub hairline: bool,
 }
 
 impl Default for FixedStroke {
@@ -610,6 +879,7 @@ impl Default for FixedStroke {
             line_join: LineJoin::Miter,
             dash_pattern: None,
             miter_limit: Scalar::new(4.0),
+            hairline: false,
         }
     }
 }
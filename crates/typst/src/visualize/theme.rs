@@ -0,0 +1,124 @@
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{At, SourceResult, StrResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    cast, elem, func, scope, ty, Content, Dict, Fold, Repr, Show, StyleChain,
+};
+use crate::syntax::Span;
+use crate::visualize::Paint;
+
+/// Named paints for themed documents.
+///
+/// A theme maps names to paints. Instead of a literal paint, the fill of a
+/// [rectangle]($rect), [square]($square), [ellipse]($ellipse), or
+/// [circle]($circle) can then refer to one of these paints by name with
+/// [`theme.paint`]($theme.paint). The name is looked up when the shape is
+/// laid out, so that a set rule can change the paint for a part of the
+/// document.
+///
+/// Only a shape's whole fill, or the result of a fill function, can refer to
+/// a paint of the theme. Strokes, the layers of a fill, the `fill-hover`
+/// property, and all other elements take literal paints.
+///
+/// # Example
+/// ```example
+/// #set theme(paints: (primary: eastern, accent: maroon))
+/// #let primary = theme.paint("primary")
+///
+/// #rect(fill: primary)[Themed]
+/// #[
+///   #set theme(paints: (primary: olive))
+///   #rect(fill: primary)[Overridden]
+/// ]
+/// ```
+#[elem(scope, Show)]
+pub struct ThemeElem {
+    /// The theme's paints by name.
+    ///
+    /// Set rules merge their paints with those of outer set rules, so that
+    /// only the given names change.
+    #[fold]
+    pub paints: ThemePaints,
+}
+
+#[scope]
+impl ThemeElem {
+    /// Refers to a paint of the theme by name.
+    ///
+    /// Laying out a shape whose fill refers to a name that no set rule of the
+    /// theme defines is an error.
+    #[func]
+    pub fn paint(
+        /// The name of the paint.
+        name: EcoString,
+    ) -> ThemePaint {
+        ThemePaint(name)
+    }
+}
+
+impl Show for ThemeElem {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+/// The paints of a theme by name.
+#[derive(Debug, Default, Clone, PartialEq, Hash)]
+pub struct ThemePaints(Vec<(EcoString, Paint)>);
+
+cast! {
+    ThemePaints,
+    self => self.0
+        .into_iter()
+        .map(|(name, paint)| (name.into(), paint.into_value()))
+        .collect::<Dict>()
+        .into_value(),
+    v: Dict => Self(v
+        .into_iter()
+        .map(|(name, paint)| Ok((name.into(), paint.cast::<Paint>()?)))
+        .collect::<StrResult<_>>()?),
+}
+
+impl Fold for ThemePaints {
+    type Output = Self;
+
+    fn fold(mut self, outer: Self::Output) -> Self::Output {
+        for (name, paint) in outer.0 {
+            if !self.0.iter().any(|(n, _)| *n == name) {
+                self.0.push((name, paint));
+            }
+        }
+        self
+    }
+}
+
+/// A reference to a paint of the [theme]($theme) by name.
+#[ty]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ThemePaint(EcoString);
+
+impl ThemePaint {
+    /// Look up the referenced paint in the theme that is active in `styles`.
+    pub fn resolve(&self, styles: StyleChain, span: Span) -> SourceResult<Paint> {
+        ThemeElem::paints_in(styles)
+            .0
+            .into_iter()
+            .find(|(name, _)| *name == self.0)
+            .map(|(_, paint)| paint)
+            .ok_or_else(|| {
+                eco_format!("theme does not define a paint named `{}`", self.0)
+            })
+            .at(span)
+    }
+}
+
+impl Repr for ThemePaint {
+    fn repr(&self) -> EcoString {
+        eco_format!("theme.paint({})", self.0.repr())
+    }
+}
+
+cast! {
+    type ThemePaint,
+}
@@ -10,6 +10,9 @@ use typst::{Library, World};
 use unscanny::Scanner;
 
 const TEXT: &str = include_str!("../typ/compiler/bench.typ");
+const SHAPES: &str = "#for i in range(1000) {
+  box(square(size: 8pt, radius: 2pt, stroke: (left: red, rest: blue)))
+}";
 const FONT: &[u8] = include_bytes!("../../assets/fonts/LinLibertine_R.ttf");
 
 main!(
@@ -20,6 +23,7 @@ main!(
     bench_eval,
     bench_compile,
     bench_render,
+    bench_shapes,
 );
 
 fn bench_decode(iai: &mut Iai) {
@@ -78,6 +82,12 @@ fn bench_render(iai: &mut Iai) {
     iai.run(|| typst_render::render(&document.pages[0], 1.0, Color::WHITE))
 }
 
+fn bench_shapes(iai: &mut Iai) {
+    let world = BenchWorld::with_text(SHAPES);
+    let mut tracer = Tracer::new();
+    iai.run(|| typst::compile(&world, &mut tracer));
+}
+
 struct BenchWorld {
     library: Prehashed<Library>,
     book: Prehashed<FontBook>,
@@ -87,6 +97,10 @@ struct BenchWorld {
 
 impl BenchWorld {
     fn new() -> Self {
+        Self::with_text(TEXT)
+    }
+
+    fn with_text(text: &str) -> Self {
         let font = Font::new(FONT.into(), 0).unwrap();
         let book = FontBook::from_fonts([&font]);
 
@@ -94,7 +108,7 @@ impl BenchWorld {
             library: Prehashed::new(Library::build()),
             book: Prehashed::new(book),
             font,
-            source: Source::detached(TEXT),
+            source: Source::detached(text),
         }
     }
 
@@ -88,6 +88,7 @@
 #test((1em + blue).join, auto)
 #test((1em + blue).dash, auto)
 #test((1em + blue).miter-limit, auto)
+#test((1em + blue).align, auto)
 
 ---
 // Test complex stroke fields.
@@ -107,6 +108,31 @@
 #test(s2.dash, (array: (3pt, "dot", 4em), phase: 0pt))
 #test(s3.dash, (array: (3pt, "dot", 4em), phase: 5em))
 
+---
+// Test that line caps survive per-side stroke folding.
+#let r = rect(stroke: (thickness: 2pt, cap: "round"))
+#test(r.stroke.cap, "round")
+#let r = rect(stroke: (left: (cap: "square"), rest: 2pt))
+#test(r.stroke.left.cap, "square")
+#test(r.stroke.top.cap, auto)
+
+---
+// Test per-corner radius fields.
+#test(rect(radius: 4pt).radius, 4pt)
+#let r = rect(radius: (top-left: 4pt, bottom-right: 8pt))
+#test(r.radius, (top-left: 4pt, bottom-right: 8pt))
+#let r = rect(radius: (left: 4pt, top-right: 8pt))
+#test(r.radius, (top-left: 4pt, top-right: 8pt, bottom-left: 4pt))
+#test(rect(radius: (x: 4pt, y: 4pt)).radius, (x: 4pt, y: 4pt))
+#let r = rect(radius: (top-left: (x: 4pt, y: 2pt)))
+#test(r.radius.top-left, (x: 4pt, y: 2pt))
+#test(r.radius.top-left.y, 2pt)
+
+---
+// Test stroke alignment field.
+#test(stroke(align: "inside").align, "inside")
+#test(rect(stroke: (thickness: 2pt, align: "outside")).stroke.align, "outside")
+
 ---
 // Test 2d alignment 'horizontal' field.
 #test((start + top).x, start)
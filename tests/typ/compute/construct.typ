@@ -308,3 +308,13 @@
 ---
 // Error: 2-36 failed to format datetime (insufficient information)
 #datetime.today().display("[hour]")
+
+---
+// Test stroke construction and representation.
+#test(stroke(dash: "dashed").dash, (array: (3, 3), phase: 0pt))
+#test(stroke((paint: red, dash: "dotted")).dash, (array: ("dot", 2), phase: 0pt))
+#test(
+  repr(stroke(thickness: 2pt, dash: (1pt, 2pt))),
+  "(thickness: 2pt, dash: (array: (1pt, 2pt), phase: 0pt))",
+)
+#test(repr(stroke(dash: none)), "(dash: none)")
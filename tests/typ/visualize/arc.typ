@@ -0,0 +1,41 @@
+// Test arcs, chords, and pie slices.
+
+---
+#set arc(radius: 12pt)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  arc(),
+  arc(start: -90deg, end: 180deg, stroke: 2pt + red),
+  arc(end: 240deg, mode: "chord", fill: blue),
+  arc(start: 30deg, end: 300deg, mode: "pie", fill: green, stroke: black),
+)
+
+---
+// A pie chart made of overlapping slices.
+#set arc(radius: 20pt, mode: "pie", stroke: white)
+#box({
+  place(arc(start: -90deg, end: 30deg, fill: red))
+  place(arc(start: 30deg, end: 150deg, fill: green))
+  arc(start: 150deg, end: 270deg, fill: blue)
+})
+
+---
+// Gradients span the whole circle, so the slices of a pie chart share a conic
+// gradient around its center.
+#set arc(radius: 20pt, mode: "pie")
+#let grad = gradient.conic(..color.map.rainbow, angle: 90deg)
+#box({
+  place(arc(start: -90deg, end: 60deg, fill: grad))
+  place(arc(start: 60deg, end: 180deg, fill: grad.sharp(3)))
+  arc(start: 180deg, end: 270deg, fill: grad, stroke: (paint: grad.sharp(8), thickness: 2pt, join: "round"))
+})
+#circle(radius: 20pt, fill: grad)
+
+---
+// Counterclockwise arc.
+#arc(start: 90deg, end: -90deg, stroke: 2pt)
+
+---
+// Error: 2-20 arc radius must be finite and non-negative
+#arc(radius: -10pt)
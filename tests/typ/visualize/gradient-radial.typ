@@ -47,3 +47,16 @@
   radius: 25pt,
   fill: gradient.radial(white, rgb("#8fbc8f"), focal-center: (75%, 35%), focal-radius: 5%),
 )
+
+---
+// Test that the gradient is centered on a circle that is laid out twice to
+// become a circle, both with and without rounded corners.
+#set page(width: 240pt)
+#let grad = gradient.radial(white, blue, black)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  circle(fill: grad, inset: 2pt)[Wide text],
+  circle(fill: grad, width: 60pt)[Hi],
+  square(fill: grad, radius: 30%, inset: 2pt)[Wide text],
+)
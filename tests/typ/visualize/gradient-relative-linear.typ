@@ -27,3 +27,37 @@
 )
 #place(top + right, my-rect)
 #place(bottom + center, rotate(45deg, my-rect))
+
+---
+// Test that linear gradients resolve against the final size of squares and
+// circles that grow to a square during relayout. Each shape should be split
+// exactly in half, both with evenly spaced and with explicit stops.
+#set page(width: 240pt)
+#let half = gradient.linear((red, 0%), (red, 50%), (blue, 50%), (blue, 100%), angle: 90deg)
+#let even = gradient.linear(red, yellow, blue, angle: 45deg)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  square(fill: half, inset: 2pt)[Wide text],
+  circle(fill: half, inset: 2pt)[Wide text],
+  square(fill: even)[Tall \ text \ here],
+  circle(fill: even, height: 40pt)[Hi],
+)
+
+---
+// Test that shapes in the same container share a parent-relative gradient,
+// including their strokes, while self-relative gradients restart in each shape.
+#set page(width: 200pt)
+#let row(relative) = {
+  let grad = gradient.linear(red, blue, relative: relative)
+  block(width: 100%, stack(
+    dir: ltr,
+    spacing: 4pt,
+    rect(width: 40pt, fill: grad),
+    square(width: 40pt, fill: grad, stroke: (bottom: 4pt + grad.sharp(2))),
+    circle(width: 40pt, fill: grad),
+    rect(width: 40pt, fill: grad, radius: 5pt),
+  ))
+}
+#row("parent")
+#row("self")
@@ -0,0 +1,13 @@
+// Test validation of gradient stops.
+
+---
+// Error: 29-41 offset must be between 0% and 100%
+#gradient.linear((red, 0%), (blue, 120%))
+
+---
+// Error: 43-54 offsets must be in strictly monotonic order
+#gradient.linear((red, 0%), (green, 60%), (blue, 40%), (red, 100%))
+
+---
+// Error: 37-39 the end radius must be greater than 0%
+#gradient.radial(red, blue, radius: 0%)
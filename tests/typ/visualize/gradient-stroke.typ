@@ -29,3 +29,21 @@
 #line(length: 100%, stroke: 1pt + gradient.linear(red, blue))
 #line(length: 100%, angle: 10deg, stroke: 1pt + gradient.linear(red, blue))
 #line(length: 100%, angle: 10deg, stroke: 1pt + gradient.linear(red, blue, relative: "parent"))
+
+---
+// Test that gradient strokes span the whole outline of rounded and partially
+// stroked shapes.
+#set page(width: 160pt, height: auto)
+#let grad = gradient.linear(red, blue)
+#stack(
+  dir: ltr,
+  spacing: 5pt,
+  rect(width: 40pt, height: 30pt, radius: 8pt, stroke: 4pt + grad),
+  rect(
+    width: 40pt,
+    height: 30pt,
+    radius: 8pt,
+    stroke: (top: 4pt + grad, bottom: 4pt + grad),
+  ),
+  ellipse(width: 40pt, height: 30pt, stroke: (left: 4pt + grad, right: 2pt + grad)),
+)
@@ -0,0 +1,27 @@
+// Test line markers.
+
+---
+#set page(width: 140pt, height: 80pt)
+#line(start: (0pt, 0pt), end: (4cm, 2cm), marker-end: "arrow")
+
+---
+// Test all markers and their scaling with the stroke.
+#set page(width: 140pt)
+#set line(length: 100%)
+#stack(
+  spacing: 12pt,
+  line(marker-start: "arrow", marker-end: "arrow"),
+  line(stroke: 2pt + blue, marker-start: "bar", marker-end: "circle"),
+  line(stroke: (paint: red, thickness: 1pt, dash: "dashed"), marker-start: "bar", marker-end: "arrow"),
+  line(marker-end: "arrow", marker-size: 10pt),
+)
+
+---
+// Markers follow the line's direction.
+#set page(width: 140pt, height: 60pt)
+#place(line(start: (50pt, 25pt), angle: 135deg, length: 30pt, marker-end: "arrow"))
+#place(line(start: (90pt, 5pt), end: (60pt, 40pt), marker-start: "circle", marker-end: "arrow"))
+
+---
+// Error: 19-26 expected "arrow", "bar", "circle", or none
+#line(marker-end: "heart")
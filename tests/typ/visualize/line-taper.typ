@@ -0,0 +1,45 @@
+// Test strokes that taper along lines and paths.
+
+---
+#set page(width: 120pt)
+#set line(length: 100%, stroke: maroon)
+#stack(
+  spacing: 8pt,
+  line(taper: (6pt, 0pt)),
+  line(taper: (0.5pt, 4pt)),
+  line(taper: (4pt, 4pt), stroke: (paint: blue, dash: "dashed")),
+  line(taper: (1pt, 4pt), marker-start: "arrow", marker-end: "arrow"),
+  line(end: (60pt, 30pt), taper: (0pt, 5pt), stroke: gradient.linear(red, blue)),
+)
+
+---
+// Curves and corners of a path taper along its whole length.
+#set page(width: 140pt)
+#path(
+  stroke: eastern,
+  taper: (0.5pt, 6pt),
+  (0pt, 30pt),
+  ((40pt, 0pt), (-20pt, 0pt)),
+  ((80pt, 30pt), (-20pt, 0pt)),
+  (120pt, 0pt),
+)
+#path(
+  fill: aqua,
+  stroke: black,
+  taper: (1pt, 5pt),
+  closed: true,
+  (0pt, 0pt),
+  (60pt, 0pt),
+  (60pt, 40pt),
+  (0pt, 40pt),
+)
+#set path(taper: (4pt, 0pt))
+#path.svg(stroke: red, "M 0 10 L 40 10 L 40 40 M 60 10 L 100 40")
+
+---
+// Error: 27-30 expected array or none, found length
+#line(length: 1cm, taper: 2pt)
+
+---
+// Error: 27-42 taper must contain exactly two lengths
+#line(length: 1cm, taper: (1pt, 2pt, 3pt))
@@ -0,0 +1,57 @@
+// Test paths defined by SVG path data.
+
+---
+#set page(width: 160pt)
+#set path(stroke: blue)
+#stack(
+  dir: ltr,
+  spacing: 10pt,
+  // Absolute lines, closed.
+  path.svg(fill: aqua, "M 0 0 L 30 0 L 30 30 Z"),
+  // Relative and implicit commands.
+  path.svg("m 0 15 l 10 -15 10 15 10 -15 10 15"),
+  // Horizontal and vertical lines.
+  path.svg(fill: yellow, "M0,0H30V10h-20v20H0z"),
+)
+
+---
+#set page(width: 160pt)
+#set path(stroke: 1pt + red)
+#stack(
+  dir: ltr,
+  spacing: 10pt,
+  // Cubic curves with a smooth continuation.
+  path.svg("M 0 20 C 0 0 20 0 20 20 S 40 40 40 20"),
+  // Quadratic curves with a smooth continuation.
+  path.svg("M 0 20 Q 10 0 20 20 T 40 20"),
+  // Multiple subpaths with a hole.
+  path.svg(fill: eastern, stroke: none, "M0 0h30v30h-30z M10 10v10h10v-10z"),
+)
+
+---
+// Test compact number syntax.
+#path.svg("M10+5e0.5.5L2e1,2e1")
+
+---
+// Error: 11-20 path data must start with a move command
+#path.svg("L 10 10")
+
+---
+// Error: 11-24 invalid `L` command at position 5: expected number, found `x`
+#path.svg("M0 0 L 10 x")
+
+---
+// Error: 11-35 invalid `A` command at position 5: elliptical arcs are not supported
+#path.svg("M0 0 A 5 5 0 0 1 10 10")
+
+---
+// Error: 11-23 invalid `C` command at position 5: expected number, found end of data
+#path.svg("M0 0 C 1 2")
+
+---
+// Error: 11-28 invalid `L` command at position 6: number `1e400` is too large
+#path.svg("M 0 0 L 1e400 0")
+
+---
+// Error: 2-39 cannot create path with infinite size
+#path.svg("M 0 0 l 1e308 0 l 1e308 0")
@@ -39,6 +39,23 @@
   ),
 )
 
+---
+// Test that the path's size accounts for curves bulging beyond the vertices.
+#set page(width: 160pt)
+#box(stroke: 0.5pt + gray, path(
+  stroke: blue,
+  ((0pt, 20pt), (0pt, 0pt), (20pt, 0pt)),
+  ((60pt, 20pt), (0pt, 20pt), (0pt, 0pt)),
+))
+#box(stroke: 0.5pt + gray, path(
+  fill: aqua,
+  stroke: blue,
+  closed: true,
+  ((0pt, 0pt), (-10pt, 0pt), (10pt, 0pt)),
+  ((40pt, 0pt), (0pt, 0pt), (0pt, 30pt)),
+  ((20pt, 20pt), (10pt, 0pt), (-10pt, 0pt)),
+))
+
 ---
 // Error: 7-9 path vertex must have 1, 2, or 3 points
 #path(())
@@ -50,3 +67,7 @@
 ---
 // Error: 7-31 point array must contain exactly two entries
 #path(((0%, 0%), (0%, 0%, 0%)))
+
+---
+// Error: 2-39 cannot create path with infinite size
+#path((0pt, 0pt), (1e308pt * 10, 0pt))
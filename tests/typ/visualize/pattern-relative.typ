@@ -21,3 +21,22 @@
 #set page(fill: pat(), width: 100pt, height: 100pt)
 
 #rect(fill: pat(relative: "parent"), width: 100%, height: 100%, stroke: 1pt)
+
+---
+// Test that the tile origin of self-relative patterns moves with the shape,
+// while parent-relative patterns stay fixed when the shape moves.
+#let pat(..args) = pattern(size: (10pt, 10pt), ..args)[
+  #place(top + left, circle(radius: 3pt, fill: blue))
+]
+
+#set page(width: 100pt, height: auto)
+#set rect(width: 25pt, height: 25pt, stroke: 0.5pt)
+#for relative in ("self", "parent") {
+  stack(
+    dir: ltr,
+    spacing: 3pt,
+    rect(fill: pat(relative: relative)),
+    rect(fill: pat(relative: relative)),
+    rect(fill: pat(relative: relative)),
+  )
+}
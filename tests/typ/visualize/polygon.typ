@@ -31,6 +31,97 @@
 // Regular polygon; should have equal side lengths
 #for k in range(3, 9) {polygon.regular(size: 30pt, vertices: k,)}
 
+---
+// Stars with varying inner radii and point counts
+#set page(width: 120pt)
+#set polygon(stroke: 0.75pt, fill: blue)
+#polygon.star(size: 30pt)
+#polygon.star(size: 30pt, points: 6, inner: 70%)
+#polygon.star(size: 30pt, points: 8, inner: 20%)
+#polygon.star(radius: 15pt, points: 4, inner: 100%)
+
+---
+// Self-intersecting polygons and paths with both fill rules
+#set page(width: 120pt)
+#let star = ((20pt, 0pt), (32pt, 36pt), (0pt, 14pt), (40pt, 14pt), (8pt, 36pt))
+#polygon(fill: blue, ..star)
+#polygon(fill: blue, fill-rule: "evenodd", ..star)
+#path(fill: blue, fill-rule: "evenodd", closed: true, ..star)
+
+---
+// Error: 21-30 expected "nonzero" or "evenodd"
+#polygon(fill-rule: "winding")
+
 ---
 // Error: 10-17 point array must contain exactly two entries
 #polygon((50pt,))
+
+---
+// Error: 28-29 regular polygon must have at least three vertices
+#polygon.regular(vertices: 2)
+
+---
+// Ref: false
+#test(
+  polygon.regular(radius: 10pt, vertices: 5).vertices,
+  polygon.regular(size: 20pt, vertices: 5).vertices,
+)
+
+---
+// Error: 23-24 star must have at least three points
+#polygon.star(points: 2)
+
+---
+// Error: 22-26 inner radius must be between 0% and 100%
+#polygon.star(inner: 150%)
+
+---
+// A star whose inner and outer radii coincide has as many vertices as a
+// regular polygon with twice as many points.
+// Ref: false
+#test(
+  polygon.star(size: 20pt, points: 4, inner: 100%).vertices.len(),
+  polygon.regular(size: 20pt, vertices: 8).vertices.len(),
+)
+
+---
+// Test the vertices of a star, which alternate between the outer and the inner
+// circle.
+// Ref: false
+#let round(vertices) = vertices.map(((x, y)) => (
+  calc.round(x / 1pt, digits: 2),
+  calc.round(y / 1pt, digits: 2),
+))
+#test(
+  round(polygon.star(size: 20pt, points: 4, inner: 50%).vertices),
+  (
+    (14.14, 14.14), (7.07, 12.07), (0.0, 14.14), (2.07, 7.07), (0.0, 0.0),
+    (7.07, 2.07), (14.14, 0.0), (12.07, 7.07), (14.14, 14.14),
+  ),
+)
+
+---
+// Test the regular polygon presets.
+#set page(width: 180pt)
+#set align(horizon)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  polygon.triangle(size: 30pt, fill: red),
+  polygon.pentagon(size: 30pt, fill: green, stroke: black),
+  polygon.hexagon(radius: 15pt, stroke: 2pt + blue),
+  polygon.octagon(size: 30pt, fill: orange),
+)
+
+---
+// Ref: false
+#test(
+  polygon.hexagon(size: 20pt).vertices,
+  polygon.regular(size: 20pt, vertices: 6).vertices,
+)
+#test(polygon.octagon().vertices.len(), polygon.regular(vertices: 8).vertices.len())
+
+---
+// Presets are sized by their circumcircle, not by a width.
+// Error: 18-29 unexpected argument: width
+#polygon.hexagon(width: 20pt)
@@ -0,0 +1,35 @@
+// Test aligning the content of shapes.
+
+---
+// Center a label in shapes of every kind.
+#set page(width: 240pt)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(width: 50pt, height: 40pt, align: center + horizon)[A],
+  square(size: 40pt, align: center + horizon)[B],
+  ellipse(width: 50pt, height: 40pt, align: center + horizon)[C],
+  circle(radius: 20pt, align: center + horizon)[D],
+)
+
+---
+// Align along one axis and at the end of the content box.
+#set page(width: 220pt)
+#set rect(width: 60pt, height: 40pt, fill: aqua)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(align: right)[R],
+  rect(align: bottom)[B],
+  rect(align: end + bottom, inset: 10pt)[E],
+)
+
+---
+// The default inherits the surrounding alignment.
+#set align(center)
+#rect(width: 80pt, height: 30pt)[Centered]
+#rect(width: 80pt, height: 30pt, align: left)[Left]
+
+---
+// Error: 26-30 expected alignment or auto, found length
+#rect(width: 1cm, align: 10pt)
@@ -61,3 +61,16 @@
 // relative to which axis.
 // Error: 15-18 expected length or auto, found ratio
 #square(size: 50%)
+
+---
+// Test custom aspect ratios for rectangles and ellipses.
+#set page(width: 120pt)
+#rect(width: 60pt, aspect: 2)
+#rect(height: 20pt, aspect: 4, fill: blue)
+#rect(aspect: 3)[Wide]
+#ellipse(aspect: 1 / 2, fill: green)
+#rect(width: 30pt, height: 10pt, aspect: 1)
+
+---
+// Error: 2-18 aspect ratio must be positive
+#rect(aspect: -2)
@@ -0,0 +1,25 @@
+// Test shifting the baseline of inline shapes.
+
+---
+#set rect(width: 6pt, height: 6pt, inset: 0pt, fill: blue)
+#set square(size: 6pt, fill: green)
+#set circle(radius: 3pt, fill: red)
+Default #box(rect()) #box(square()) #box(circle()) x \
+Shifted #box(rect(baseline: 2pt)) #box(square(baseline: 50%)) x \
+Centered #box(rect(baseline: auto)) #box(square(baseline: auto))
+#box(circle(baseline: auto)) #box(ellipse(width: 10pt, height: 4pt, baseline: auto)) x
+
+---
+// The baseline is shifted after rotation.
+#set text(size: 20pt)
+A #box(rect(width: 10pt, height: 10pt, rotate: 45deg, baseline: auto)) B
+
+---
+// A shape with content is centered as a whole.
+#set text(size: 8pt)
+Tag #box(rect(inset: 2pt, radius: 2pt, baseline: auto)[new]) here
+
+---
+// Other blocks don't pass their baseline on to a box.
+#set block(fill: aqua, inset: 2pt, spacing: 0pt)
+A #box(block[B]) C #box[$ x^2 $] D #box(rect(fill: aqua)[E]) F
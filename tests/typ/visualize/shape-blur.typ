@@ -0,0 +1,37 @@
+// Test blurred shape fills.
+
+---
+#set page(width: 160pt)
+#set align(center)
+#stack(
+  dir: ltr,
+  spacing: 12pt,
+  rect(width: 30pt, height: 30pt, fill: orange, blur: 8pt),
+  circle(radius: 15pt, fill: blue, blur: 10pt),
+  square(size: 30pt, fill: green, blur: 6pt, radius: 5pt, stroke: black),
+)
+
+---
+// A blur respects the opacity and works with gradients.
+#set page(width: 160pt)
+#stack(
+  dir: ltr,
+  spacing: 12pt,
+  ellipse(width: 50pt, height: 30pt, fill: rgb(255, 0, 0, 50%), blur: 10pt),
+  rect(width: 50pt, height: 30pt, fill: gradient.linear(red, blue), blur: 6pt, opacity: 50%),
+)
+
+---
+// A zero blur leaves the fill untouched.
+#rect(fill: aqua, blur: 0pt)
+
+---
+// A blur larger than the shape only leaves a faint, soft dot.
+#set page(width: 160pt)
+#stack(
+  dir: ltr,
+  spacing: 24pt,
+  rect(width: 4pt, height: 4pt, fill: red, blur: 20pt),
+  circle(radius: 2pt, fill: blue, blur: 20pt),
+  square(size: 6pt, fill: green, radius: 2pt, blur: 30pt, shadow: (dx: 8pt, blur: 20pt)),
+)
@@ -0,0 +1,47 @@
+// Test breakable rectangles.
+
+---
+// Test that a breakable rectangle continues on the next page and that only
+// its outer corners are rounded.
+#set page(height: 120pt)
+#set text(8pt)
+#v(40pt)
+#rect(
+  breakable: true,
+  width: 100%,
+  radius: 8pt,
+  fill: aqua,
+  stroke: 1pt + blue,
+  lorem(60),
+)
+
+---
+// Test a breakable rectangle with fixed height.
+#set page(height: 80pt)
+#rect(
+  breakable: true,
+  width: 50%,
+  height: 120pt,
+  fill: eastern,
+  radius: 5pt,
+)[Top]
+
+---
+// Test that rectangles are not breakable by default.
+#set page(height: 80pt)
+#set text(8pt)
+Hello
+#rect(fill: aqua, lorem(20))
+
+---
+// Test that a stroked rectangle broken across three columns only rounds the
+// top corners of its first and the bottom corners of its last part.
+#set page(width: 180pt, height: 90pt, columns: 3)
+#set text(6pt)
+#rect(
+  breakable: true,
+  width: 100%,
+  radius: 10pt,
+  stroke: 2pt + red,
+  lorem(40),
+)
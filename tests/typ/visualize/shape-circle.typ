@@ -56,3 +56,52 @@ Expanded by height.
 // Radius wins over width and height.
 // Error: 23-34 unexpected argument: width
 #circle(radius: 10pt, width: 50pt, height: 100pt, fill: eastern)
+
+---
+// Test that a tight inset mode avoids excessive padding for short labels.
+#set page(width: 120pt)
+#set circle(stroke: 0.5pt, fill: aqua)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  circle[A],
+  circle(inset-mode: "tight")[A],
+  circle(inset-mode: "tight", inset: 2pt)[12],
+)
+#circle(width: 50pt, inset-mode: "tight", align(center + horizon)[Label])
+
+---
+// Error: 22-29 expected "circle" or "tight"
+#ellipse(inset-mode: "loose")
+
+---
+// Test rings.
+#set circle(radius: 12pt)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  circle(hole-radius: 6pt, fill: eastern),
+  circle(hole-radius: 75%, fill: red, stroke: 1pt),
+  circle(hole-radius: 50%, stroke: (left: 2pt + blue, right: 2pt + green)),
+  circle(hole-radius: 50%, fill: gradient.linear(red, blue), blur: 2pt),
+)
+
+---
+// Error: 2-41 hole radius must be smaller than the circle's radius
+#circle(radius: 10pt, hole-radius: 10pt)
+
+---
+// Test the diameter as an alternative to the radius.
+#set page(width: 120pt)
+#circle(diameter: 20pt, fill: eastern)
+#circle(diameter: 30pt, fill: eastern)[Wide content]
+#circle(diameter: auto, fill: eastern)[Auto]
+#style(styles => {
+  let size = measure(circle(diameter: 30pt)[Wide content], styles)
+  test(size, (width: 30pt, height: 30pt))
+  test(size, measure(circle(radius: 15pt)[Wide content], styles))
+})
+
+---
+// Error: 32-36 cannot set both radius and diameter
+#circle(radius: 5pt, diameter: 10pt)
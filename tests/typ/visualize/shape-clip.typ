@@ -0,0 +1,63 @@
+// Test clipping content to a shape's outline.
+
+---
+#set page(height: 80pt)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  rect(
+    radius: 6pt,
+    stroke: 2pt + black,
+    width: 24pt,
+    height: 24pt,
+    inset: 0pt,
+    clip: true,
+    image("/files/rhino.png", width: 40pt),
+  ),
+  circle(
+    radius: 12pt,
+    stroke: 2pt + blue,
+    inset: 0pt,
+    clip: true,
+    image("/files/rhino.png", width: 40pt),
+  ),
+  ellipse(
+    width: 30pt,
+    height: 20pt,
+    outset: 2pt,
+    clip: true,
+    fill: yellow,
+    rect(width: 40pt, height: 40pt, fill: red),
+  ),
+)
+
+---
+// Without clipping, content overflows the circle.
+#set page(height: 60pt)
+#circle(radius: 12pt, inset: 0pt, image("/files/rhino.png", width: 40pt))
+
+---
+// Links in clipped content only react within the visible area.
+#set page(width: 200pt)
+#let target = link("https://typst.app", box(width: 60pt, height: 60pt, fill: aqua))
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  circle(radius: 20pt, inset: 0pt, clip: true, target),
+  rect(width: 40pt, height: 40pt, radius: 10pt, inset: 0pt, clip: true, target),
+  rect(width: 40pt, height: 40pt, inset: 0pt, clip: true, move(dx: 20pt, dy: 20pt, target)),
+  rect(width: 40pt, height: 40pt, inset: 0pt, clip: false, target),
+)
+
+---
+// Content that overflows its inset is clipped by the rounded corners, both
+// with a uniform and an elliptical radius and with a stroke.
+#set page(width: 200pt)
+#let big = move(dx: -30pt, dy: -30pt, rect(width: 100pt, height: 100pt, fill: red))
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(width: 50pt, height: 50pt, inset: 10pt, radius: 25pt, clip: true, fill: aqua, big),
+  rect(width: 50pt, height: 50pt, inset: 10pt, radius: 20pt, stroke: 4pt + blue, clip: true, big),
+  rect(width: 60pt, height: 40pt, inset: 10pt, radius: (x: 50%, y: 50%), clip: true, big),
+)
@@ -0,0 +1,58 @@
+// Test configurable default sizes of shapes without a body.
+
+---
+#set page(width: 180pt)
+#set rect(default-size: (20pt, 10pt))
+#set ellipse(default-size: (30pt, 1em))
+#set square(default-size: (40pt, 15pt))
+#set circle(default-diameter: 10pt)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  rect(),
+  rect(width: 30pt),
+  ellipse(),
+  square(),
+  circle(),
+  rect[Body],
+)
+
+---
+// A circle has a dedicated default diameter.
+#style(styles => {
+  test(measure(circle(), styles), (width: 30pt, height: 30pt))
+  test(measure(circle(default-diameter: 2em), styles).width, 20pt)
+  test(measure(circle(width: 16pt), styles).width, 16pt)
+})
+
+---
+// The default size is limited to the available space.
+#set page(width: 100pt)
+#rect(default-size: (200pt, 10pt), fill: aqua)
+
+---
+// Error: 21-25 expected array, found length
+#rect(default-size: 10pt)
+
+---
+// Error: 27-39 expected length, found array
+#circle(default-diameter: (10pt, 10pt))
+
+---
+// A size along only one axis leaves the other one at its default, and a
+// single side determines the size of a square or circle.
+#style(styles => {
+  test(measure(rect(width: 80pt), styles), (width: 80pt, height: 30pt))
+  test(measure(rect(height: 50pt), styles), (width: 45pt, height: 50pt))
+  test(measure(ellipse(width: 80pt), styles), (width: 80pt, height: 30pt))
+  test(measure(square(width: 80pt), styles), (width: 80pt, height: 80pt))
+  test(measure(square(height: 10pt), styles), (width: 10pt, height: 10pt))
+  test(measure(circle(width: 80pt), styles), (width: 80pt, height: 80pt))
+})
+
+---
+// Filling only the width keeps the default height.
+#set page(width: 120pt)
+#rect(width: 1fr, fill: aqua)
+#ellipse(width: 100%, fill: aqua)
+#square(width: 50%, fill: aqua)
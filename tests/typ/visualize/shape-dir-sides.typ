@@ -0,0 +1,31 @@
+// Test start and end keys for insets, outsets, and strokes.
+
+---
+#set page(width: 120pt)
+#let body = rect(width: 100%, height: 10pt, fill: aqua, stroke: none)
+#let side(..args) = rect(
+  width: 100%,
+  inset: (start: 20pt, rest: 2pt),
+  stroke: (start: 3pt + red, rest: 1pt),
+  ..args,
+  body,
+)
+
+#side()
+#set text(dir: rtl)
+#side()
+#side(inset: (left: 10pt, end: 0pt), stroke: (x: blue))
+
+---
+// Start and end take precedence over physical sides.
+#set text(dir: rtl)
+#rect(width: 60pt, height: 20pt, outset: (right: 5pt, start: 0pt), stroke: (rest: 1pt, end: 4pt + green))
+
+---
+#test(rect(inset: (start: 1pt)).inset, (start: 1pt))
+#test(rect(inset: (left: 1pt)).inset, (left: 1pt))
+#test(rect(stroke: (start: red)).stroke, (start: stroke(red)))
+
+---
+// Error: 14-34 unexpected key "begin", valid keys are "left", "top", "right", "bottom", "x", "y", "rest", "start", and "end"
+#rect(inset: (begin: 1pt, x: 2pt))
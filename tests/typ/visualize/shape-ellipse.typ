@@ -29,3 +29,14 @@ Auto-sized ellipse.
 An inline
 #box(ellipse(width: 8pt, height: 6pt, outset: (top: 3pt, rest: 5.5pt)))
 ellipse.
+
+---
+// Test per-side strokes.
+#set ellipse(width: 30pt, height: 20pt)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  ellipse(stroke: (top: 2pt + red)),
+  ellipse(stroke: (top: 2pt + red, bottom: 2pt + blue), fill: yellow),
+  circle(radius: 10pt, stroke: (x: 3pt + green, rest: (dash: "dotted"))),
+)
@@ -0,0 +1,24 @@
+// Test automatic fills that follow the text color.
+
+---
+#set page(width: 120pt)
+#let dot = box(circle(radius: 3pt, fill: auto))
+#dot Default
+#text(fill: blue)[#dot Blue]
+#set text(fill: gradient.linear(red, blue))
+#rect(width: 100%, height: 10pt, fill: auto)
+
+---
+// An automatic fill removes the default stroke.
+#set text(fill: eastern)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  square(size: 20pt, fill: auto),
+  ellipse(width: 30pt, height: 20pt, fill: auto, stroke: black),
+  square(size: 20pt),
+)
+
+---
+#test(rect(fill: auto).fill, auto)
+#test(rect(fill: none).fill, none)
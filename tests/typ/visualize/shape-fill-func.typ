@@ -0,0 +1,34 @@
+// Test fills that depend on the shape's size.
+
+---
+#set page(width: 160pt)
+#let warn(size) = if size.width > 60pt { red } else { green }
+#rect(fill: warn)[Short]
+#rect(fill: warn)[A somewhat longer text]
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  circle(radius: 8pt, fill: size => if size.width < 20pt { blue }),
+  circle(radius: 12pt, fill: size => if size.width < 20pt { blue }),
+  square(size: 20pt, fill: size => gradient.linear(red, blue).sample(size.width / 40pt * 100%)),
+)
+
+---
+// A function fill removes the default stroke even if it returns none.
+#rect(width: 20pt, height: 10pt, fill: size => none)
+
+---
+// Test that the function is called for each part of a broken shape.
+#set page(width: 100pt, height: 80pt)
+#v(30pt)
+#rect(breakable: true, width: 100%, fill: size => if size.height > 40pt { aqua } else { yellow })[
+  #lorem(15)
+]
+
+---
+// Error: 2-32 fill function must return a paint, an image, or none
+#rect(fill: size => (s => red))
+
+---
+// Error: 2-23 expected color, gradient, pattern, theme paint, function, array, content, or none, found integer
+#rect(fill: size => 5)
@@ -0,0 +1,13 @@
+// Test that alternative hover fills don't change static output.
+
+---
+#set page(width: 200pt)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(fill: blue, fill-hover: red),
+  square(size: 30pt, fill: green, fill-hover: gradient.linear(red, blue)),
+  circle(radius: 15pt, fill-hover: red),
+  ellipse(width: 30pt, height: 20pt, fill: aqua, fill-hover: none),
+)
+#test(rect(fill-hover: red).fill-hover, red)
@@ -0,0 +1,45 @@
+// Test image fills for shapes.
+
+---
+// Test the different fit modes.
+#set page(width: 160pt)
+#set rect(width: 60pt, height: 20pt, stroke: 1pt)
+#rect(fill: image("/files/rhino.png", fit: "cover"))
+#rect(fill: image("/files/rhino.png", fit: "contain"))
+#rect(fill: image("/files/rhino.png", fit: "stretch"))
+
+---
+// Test that image fills are clipped to the shape's outline.
+#set page(width: 160pt)
+#stack(
+  dir: ltr,
+  spacing: 5pt,
+  rect(
+    width: 40pt,
+    height: 40pt,
+    radius: 50%,
+    fill: image("/files/tiger.jpg"),
+  ),
+  circle(radius: 20pt, stroke: 2pt + blue, fill: image("/files/tiger.jpg")),
+  ellipse(width: 50pt, height: 30pt, fill: image("/files/rhino.png")),
+)
+
+---
+// Test image fills with body and outset.
+#rect(
+  inset: 8pt,
+  outset: 4pt,
+  radius: 6pt,
+  fill: image("/files/rhino.png"),
+  text(white)[*Rhino*],
+)
+
+---
+// Test that image fills can be read back.
+#let img = image("/files/rhino.png")
+#test(rect(fill: img).fill, img)
+#test(rect(fill: red).fill, red)
+
+---
+// Error: 13-17 expected paint or image
+#rect(fill: [Hi])
@@ -91,3 +91,55 @@
   h(0.2cm),
 	square(radius: (top-left: 0pt, rest: 100pt)),
 )
+
+---
+// Test that an explicit `none` stroke overrides an inherited stroke, while
+// `auto` falls back to the default.
+#set rect(width: 12pt, height: 10pt, stroke: 2pt + red)
+#test(rect(stroke: none).stroke, none)
+#test(rect(stroke: auto).stroke, auto)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  rect(),
+  rect(stroke: none),
+  rect(stroke: none, fill: teal),
+  rect(stroke: auto),
+  rect(stroke: auto, fill: teal),
+  {
+    set rect(stroke: none)
+    rect(stroke: blue)
+  },
+)
+
+---
+// Test layered fills.
+#set rect(width: 24pt, height: 16pt)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  rect(fill: (teal,)),
+  rect(fill: (teal, rgb(255, 255, 255, 50%))),
+  rect(fill: (teal, gradient.linear(rgb(0, 0, 0, 0%), rgb(0, 0, 0, 60%))), stroke: 2pt + red, radius: 4pt),
+  circle(radius: 8pt, fill: (red, rgb(0, 0, 255, 50%)), stroke: 1pt),
+)
+#test(rect(fill: (red,)).fill, red)
+#test(rect(fill: (red, blue)).fill, (red, blue))
+
+---
+// Error: 13-15 expected at least one paint
+#rect(fill: ())
+
+---
+// Test drawing the stroke on top of the content.
+#set page(width: 140pt)
+#set rect(inset: 0pt, fill: aqua, stroke: 4pt + red)
+#set circle(inset: 0pt, stroke: 4pt + red)
+#let body = rect(width: 30pt, height: 20pt, fill: teal, stroke: none)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  rect(body),
+  rect(stroke-over: true, body),
+  circle(stroke-over: true, fill: (aqua, rgb(0, 0, 0, 20%)), square(size: 20pt, fill: teal)),
+)
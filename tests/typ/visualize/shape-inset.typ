@@ -0,0 +1,27 @@
+// Test negative insets, which let the content overflow the shape.
+
+---
+#set page(width: 120pt)
+A #box(rect(inset: -4pt, stroke: red, stroke-over: true, image("/files/tiger.jpg", width: 40pt))) B
+#rect(inset: -4pt, stroke: red, rect(width: 100%, height: 10pt, fill: rgb(0, 0, 255, 50%)))
+#rect(inset: -10%, width: 60pt, height: 30pt, stroke: red)[
+  #rect(width: 100%, height: 100%, fill: rgb(0, 0, 255, 50%))
+]
+
+---
+// The shape's size excludes the overflow.
+#style(styles => {
+  let size = measure(rect(inset: -4pt, box(width: 20pt, height: 10pt)), styles)
+  test(size, (width: 12pt, height: 2pt))
+})
+
+---
+// Content that is smaller than the overflow collapses the shape to zero size
+// around the content's center.
+#style(styles => {
+  let size = measure(rect(inset: -10pt, box(width: 4pt, height: 4pt)), styles)
+  test(size, (width: 0pt, height: 0pt))
+})
+#set page(width: 60pt)
+#box(stroke: 0.5pt + green, circle(inset: -10pt, stroke: red, box(width: 4pt, height: 4pt, fill: blue)))
+#box(stroke: 0.5pt + green, pad(x: -6pt, box(width: 8pt, height: 8pt, fill: blue)))
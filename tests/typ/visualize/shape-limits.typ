@@ -0,0 +1,49 @@
+// Test minimum and maximum sizes of shapes.
+
+---
+// Test that auto-sized rectangles stay within their limits.
+#set page(width: 160pt)
+#set rect(min-width: 50pt, max-width: 90pt, min-height: 20pt)
+#rect[Short]
+#rect[This text is too long to fit into a single line]
+#rect(width: 120pt)[Clamped]
+#rect(max-height: 10pt, min-height: 5pt)
+
+---
+// Test limits with relative lengths and ellipses.
+#set page(width: 160pt)
+#ellipse(min-width: 50%, fill: teal)[Wide]
+#ellipse(max-width: 30pt, max-height: 15pt, fill: teal)
+
+---
+// Test that limits apply to the common side length of squares and circles.
+#set page(width: 160pt)
+#stack(
+  dir: ltr,
+  spacing: 5pt,
+  square(min-width: 30pt)[A],
+  square(min-height: 40pt, max-width: 50pt)[B],
+  circle(max-width: 25pt, fill: eastern),
+  circle(min-height: 35pt)[C],
+)
+
+---
+// Test the limits of a square with content that doesn't fit.
+#square(max-width: 30pt, fill: aqua)[Some text in a square]
+
+---
+// Error: 2-40 minimum width must not exceed maximum width
+#rect(min-width: 20pt, max-width: 10pt)
+
+---
+// Error: 2-43 minimum height must not exceed maximum height
+#ellipse(min-height: 1cm, max-height: 5mm)
+
+---
+// Error: 2-43 minimum size must not exceed maximum size
+#square(min-width: 20pt, max-height: 10pt)
+
+---
+// Test that the limits can be read back.
+#test(rect(min-width: 5pt).min-width, 5pt)
+#test(rect(max-height: 50%).max-height, 50%)
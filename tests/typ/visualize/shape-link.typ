@@ -0,0 +1,15 @@
+// Test links that only react within a shape's outline.
+
+---
+#set page(width: 200pt)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  link("https://typst.app", circle(radius: 20pt, fill: blue, link-shape: true)),
+  link("https://typst.app", rect(radius: 10pt, fill: aqua, link-shape: true)[Click]),
+  link("https://typst.app", square(size: 40pt, fill: teal, link-shape: true)),
+  link(
+    "https://typst.app",
+    ellipse(width: 50pt, height: 30pt, fill: eastern, rotate: 20deg, link-shape: true),
+  ),
+)
@@ -0,0 +1,28 @@
+// Test measuring rectangles without drawing them.
+
+---
+// Equal-width buttons.
+#set page(width: 160pt)
+#let buttons(..labels) = style(styles => {
+  let labels = labels.pos()
+  let width = calc.max(..labels.map(it => rect.measure(it, styles).width))
+  for label in labels {
+    box(rect(width: width, fill: aqua, label))
+  }
+})
+
+#buttons[Ok][Cancel][Apply all]
+
+---
+// The measured size includes the inset, but not the stroke or outset.
+#set rect(inset: 4pt, outset: 3pt, stroke: 10pt)
+#style(styles => {
+  let size = rect.measure(box(width: 20pt, height: 10pt), styles)
+  test(size, (width: 28pt, height: 18pt))
+  test(size, measure(rect(box(width: 20pt, height: 10pt)), styles))
+})
+
+---
+// A fill function is not called when measuring.
+#set rect(fill: size => panic("fill called"))
+#style(styles => test(rect.measure([], styles).width, 10pt))
@@ -0,0 +1,36 @@
+// Test shape opacity.
+
+---
+#set page(width: 120pt)
+#set rect(width: 40pt, height: 25pt, opacity: 50%)
+#rect(fill: blue, stroke: 4pt + black)
+#place(dx: 20pt, dy: -20pt, rect(fill: red))
+#place(dx: 40pt, dy: -40pt, circle(radius: 10pt, fill: green, opacity: 30%))
+#rect(fill: gradient.linear(red, blue), opacity: 50%)
+#rect(fill: luma(0), opacity: 0%)
+
+---
+// Shadows fade along with the shape.
+#square(size: 20pt, fill: blue, opacity: 40%, shadow: (dx: 4pt, dy: 4pt))
+
+---
+// Patterns and images stay opaque.
+#let pat = pattern(size: (10pt, 10pt), square(size: 5pt, fill: blue))
+// Warning: 2-43 opacity is not applied to patterns and images
+// Hint: 2-43 patterns and images are painted fully opaque
+#rect(width: 30pt, fill: pat, opacity: 0%)
+// Warning: 2-80 opacity is not applied to patterns and images
+// Hint: 2-80 patterns and images are painted fully opaque
+#rect(width: 30pt, fill: image("/files/tiger.jpg"), opacity: 50%, stroke: none)
+// Warning: 2-53 opacity is not applied to patterns and images
+// Hint: 2-53 patterns and images are painted fully opaque
+#square(size: 20pt, stroke: 4pt + pat, opacity: 50%)
+
+---
+// CMYK colors are kept unless they are made translucent.
+#set page(width: 120pt)
+#rect(width: 30pt, fill: cmyk(0%, 60%, 100%, 0%), stroke: 2pt + cmyk(0%, 0%, 0%, 100%))
+#test(rect(fill: cmyk(0%, 60%, 100%, 0%)).fill, cmyk(0%, 60%, 100%, 0%))
+// Warning: 2-64 opacity converts CMYK colors to RGB
+// Hint: 2-64 set the opacity to 100% to keep CMYK colors in the PDF
+#rect(width: 30pt, fill: cmyk(0%, 60%, 100%, 0%), opacity: 50%)
@@ -0,0 +1,70 @@
+// Test positive and negative outsets of shapes.
+
+---
+// Test that negative outsets shrink the painted shape but not the layout.
+#set page(width: 160pt)
+#set rect(width: 30pt, height: 30pt, fill: aqua, stroke: 1pt + blue)
+#stack(
+  dir: ltr,
+  spacing: 5pt,
+  rect(),
+  rect(outset: -2pt),
+  rect(outset: -5pt, radius: 5pt),
+  rect(outset: (x: -10pt, top: -2pt, bottom: 4pt)),
+)
+
+---
+// Test negative outsets with ellipses, circles, and content.
+#set page(width: 160pt)
+#stack(
+  dir: ltr,
+  spacing: 5pt,
+  ellipse(outset: -2pt, fill: eastern)[Hi],
+  circle(outset: -4pt, fill: eastern, shadow: (dx: 2pt, dy: 2pt)),
+  square(outset: -25%, fill: eastern, clip: true)[ABC],
+)
+
+---
+// Test that outsets beyond the shape's size don't produce negative sizes.
+#set page(width: 160pt)
+#stack(
+  dir: ltr,
+  spacing: 5pt,
+  [A],
+  rect(width: 30pt, height: 20pt, outset: -20pt, stroke: 2pt),
+  ellipse(width: 30pt, height: 20pt, outset: (x: -20pt), fill: red),
+  box(width: 30pt, height: 20pt, outset: -12pt, fill: green),
+  [B],
+)
+
+---
+// Test axis shorthands for outsets and that specific sides win over them.
+#test(rect(outset: (x: 2pt, y: 0pt)).outset, (left: 2pt, top: 0pt, right: 2pt, bottom: 0pt))
+#test(rect(outset: (x: 2pt, left: 6pt)).outset, (left: 6pt, right: 2pt))
+#set page(width: 160pt)
+#set rect(width: 30pt, height: 20pt, fill: aqua)
+#stack(
+  dir: ltr,
+  spacing: 12pt,
+  [A],
+  rect(outset: (x: 5pt, y: 0pt)),
+  rect(outset: (x: 5pt, left: 0pt, bottom: 5pt), radius: 4pt),
+  [B],
+)
+
+---
+// Test large asymmetric outsets, like for a speech bubble. The painted shape
+// extends to the right only, while the text stays where it is laid out.
+#set page(width: 160pt)
+#set rect(fill: aqua, stroke: 1pt + blue, radius: 4pt)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  rect(outset: (left: 0pt, right: 20pt))[Hi],
+  [A],
+  h(20pt),
+  rect(outset: (left: 0pt, right: 20pt, bottom: 10pt), shadow: (dx: 2pt, dy: 2pt))[Hey],
+  h(20pt),
+  [B],
+)
+
@@ -46,6 +46,47 @@
 #set rect(stroke: (right: red))
 #rect(width: 100%, fill: lime, stroke: (x: 5pt, y: 1pt))
 
+---
+// Test that per-side strokes set in separate scopes compose.
+#set page(width: 140pt)
+#set rect(width: 30pt, height: 20pt, inset: 0pt)
+#set rect(stroke: (left: 2pt + red))
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(),
+  {
+    set rect(stroke: (top: 4pt + blue))
+    rect()
+    v(8pt)
+    set rect(stroke: (left: 4pt))
+    rect()
+  },
+  rect(stroke: (bottom: green)),
+)
+
+---
+// Test axis shorthands for the inset and that specific sides win over them.
+#test(rect(inset: (x: 8pt, y: 4pt)).inset, (left: 8pt, top: 4pt, right: 8pt, bottom: 4pt))
+#test(rect(inset: (x: 8pt, top: 2pt)).inset, (left: 8pt, top: 2pt, right: 8pt))
+#test(rect(inset: (top: 2pt, x: 8pt, rest: 1pt)).inset, (left: 8pt, top: 2pt, right: 8pt, bottom: 1pt))
+#rect(inset: (x: 8pt, top: 2pt), fill: aqua)[Text]
+
+---
+// Test that shapes without area paint nothing.
+#set page(width: 120pt)
+A#box(rect(width: 0pt, height: 20pt, stroke: 2pt + red))B
+A#box(ellipse(width: 20pt, height: 0pt, fill: red, stroke: red))B
+A#box(rect(width: 0pt, height: 0pt, shadow: (blur: 4pt)))B
+A#box(rect(width: 0pt, height: 10pt, outset: (x: 3pt), fill: blue))B
+
+---
+// Test that non-uniform strokes without a fill paint the stroked sides.
+#set page(width: 120pt)
+#rect(stroke: (bottom: 1pt + blue), inset: (x: 0pt, y: 2pt))[Underlined]
+#rect(stroke: (left: 2pt, right: 2pt + red), radius: 4pt)[Sides]
+#ellipse(stroke: (bottom: 2pt), width: 60pt, height: 20pt)
+
 ---
 // Error: 15-38 unexpected key "cake", valid keys are "top-left", "top-right", "bottom-right", "bottom-left", "left", "top", "right", "bottom", and "rest"
 #rect(radius: (left: 10pt, cake: 5pt))
@@ -61,3 +102,69 @@
 #set par(justify: true)
 #lorem(100)
 #rect(lorem(100))
+
+---
+// Test outlining content regardless of set rules for rectangles.
+#set rect(fill: red, inset: 10pt, stroke: (left: 4pt))
+Some #box(rect.outline[boxed]) text.
+#rect.outline(stroke: 2pt + blue)[Warning]
+#rect.outline(stroke: (dash: "dashed"), image("/files/tiger.jpg", width: 40pt))
+
+---
+// Test that strokes which differ only in paint or only in thickness are both
+// drawn per side.
+#set page(width: 220pt)
+#set rect(width: 40pt, height: 25pt)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(stroke: (left: 2pt + red, top: 2pt + green, right: 2pt + blue, bottom: 2pt + red)),
+  rect(stroke: (left: 1pt, top: 2pt, right: 3pt, bottom: 4pt)),
+  rect(stroke: (left: 2pt + red, rest: 2pt + blue), radius: 6pt),
+  rect(stroke: (x: 1pt, y: 3pt), radius: 6pt),
+)
+
+---
+// Test that relative insets are relative to the rectangle's own size, also
+// when the size is automatic or limited.
+#set page(width: 200pt)
+#set rect(inset: 10%, fill: aqua)
+#let inner = rect(width: 100%, height: 100%, fill: white, inset: 0pt)
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  rect(width: 50pt, height: 30pt, inner),
+  rect(inset: (x: 10%, y: 0%))[Auto],
+  rect(min-width: 50pt, min-height: 30pt, square(size: 10pt, fill: white)),
+)
+
+---
+// Test relative and fractional sizes.
+#block(width: 100pt, height: 60pt, stroke: 0.5pt)[
+  #rect(width: 50%, height: 20pt, fill: aqua)
+  #rect(width: 1fr, height: 1fr, fill: teal)
+]
+#grid(
+  columns: (1fr, 2fr),
+  rows: 30pt,
+  gutter: 4pt,
+  rect(width: 1fr, height: 1fr, fill: eastern),
+  square(width: 1fr, fill: forest),
+)
+#test(rect(height: 2fr).height, 2fr)
+
+---
+// Test growing rectangles.
+#set rect(fill: aqua)
+#rect(grow: true)[Fills the width]
+#block(height: 40pt, stroke: 0.5pt, {
+  rect(grow: true, fill: teal)[Fills the rest]
+})
+#rect(grow: false, width: 50%)[Sized]
+
+---
+// Test that only fractionally sized shapes fill the remaining height.
+#block(width: 100pt, height: 60pt, stroke: 0.5pt)[
+  Above
+  #image("/files/tiger.jpg")
+]
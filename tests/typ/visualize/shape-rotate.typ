@@ -0,0 +1,50 @@
+// Test rotating and skewing shapes in place.
+
+---
+#set rect(width: 30pt, height: 15pt, fill: blue)
+A #box(rect(rotate: 30deg)) B #box(rect(rotate: 90deg)) C
+
+#square(size: 15pt, rotate: 45deg, stroke: 2pt + red)[A]
+#ellipse(width: 30pt, height: 15pt, rotate: -45deg, fill: green)
+
+---
+// Links apply to the whole rotated shape.
+#link("https://typst.app", rect(rotate: 20deg, fill: yellow)[Link])
+
+---
+// Skewed shapes grow their frame to fit, also when they are rotated.
+#set page(width: 160pt)
+#let framed(shape) = box(stroke: 0.5pt + red, shape)
+#framed(rect(skew: -20deg, fill: aqua)[Slanted])
+#framed(rect(skew: 30deg, width: 20pt, height: 20pt, fill: blue))
+#framed(ellipse(skew: 20deg, rotate: 45deg, width: 30pt, height: 15pt, fill: green))
+
+---
+// Error: 2-22 skew must be between -90deg and 90deg
+#rect(skew: 90deg)[A]
+
+---
+// Test rotating only the content versus the whole shape.
+#set page(width: 260pt)
+#set rect(width: 40pt, height: 40pt, inset: 0pt, fill: aqua)
+#let body = align(center + horizon)[Text]
+#stack(
+  dir: ltr,
+  spacing: 12pt,
+  rect(body),
+  rect(rotate: 45deg, body),
+  rect(content-rotate: 45deg, body),
+  rect(content-rotate: 45deg, rotate: -45deg, body),
+)
+
+---
+// Rotated content can be clipped to the stationary outline, also for round
+// shapes.
+#set page(width: 160pt)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(width: 50pt, height: 30pt, clip: true, content-rotate: 30deg, fill: aqua, image("/files/rhino.png", width: 50pt)),
+  circle(radius: 20pt, clip: true, content-rotate: 90deg, fill: aqua, inset: 0pt, image("/files/tiger.jpg", width: 40pt)),
+  square(size: 40pt, content-rotate: 180deg, align: center + horizon)[Up],
+)
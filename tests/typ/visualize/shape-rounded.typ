@@ -51,3 +51,92 @@
   h(10pt),
   square(radius: 55%),
 )
+
+---
+// Test elliptical corners.
+#set rect(width: 60pt, height: 30pt, stroke: 2pt)
+#rect(radius: (x: 20pt, y: 8pt))
+#rect(radius: (x: 50%, y: 40%))
+#rect(
+  radius: (top-left: (x: 30pt, y: 10pt), bottom-right: 6pt),
+  stroke: (left: red, top: 3pt + blue, right: green, bottom: 1pt),
+)
+
+---
+// An elliptical radius with equal components is still relative to the width
+// and height, unlike a single radius.
+#set page(width: 100pt)
+#set rect(width: 60pt, height: 30pt, stroke: 2pt)
+#rect(radius: (x: 50%, y: 50%))
+#rect(radius: 50%)
+#test(rect(radius: (x: 50%, y: 50%)).radius, (x: 50%, y: 50%))
+#test(rect(radius: 50%).radius, 50%)
+
+---
+// Error: 15-23 dictionary does not contain key "y"
+#rect(radius: (x: 2pt))
+
+---
+// Test that radii which overlap along a side are all scaled down by the same
+// factor, so that adjacent corners never overlap.
+#set rect(width: 60pt, height: 30pt, stroke: 2pt)
+#rect(radius: 100%)
+#rect(radius: (top-left: 100%, top-right: 100%, bottom-right: 100%, bottom-left: 100%))
+#rect(radius: (top-left: 40pt, top-right: 80pt))
+#square(size: 30pt, radius: (top-left: 100%))
+
+---
+// Test that a percentage radius is relative to the shorter side, such that
+// 50% produces a pill regardless of the orientation.
+#set rect(stroke: 2pt, fill: aqua, radius: 50%)
+#rect(width: 80pt, height: 20pt)
+#stack(
+  dir: ltr,
+  spacing: 6pt,
+  rect(width: 16pt, height: 50pt),
+  rect(width: 30pt, height: 30pt),
+  rect(width: 40pt, height: 14pt, stroke: (left: red, right: blue)),
+)
+
+---
+// Test that a single length rounds all four corners equally.
+#test(rect(radius: 6pt).radius, 6pt)
+#set rect(width: 30pt, height: 20pt, stroke: 2pt)
+#stack(
+  dir: ltr,
+  spacing: 6pt,
+  rect(radius: 6pt),
+  rect(radius: (rest: 6pt)),
+  {
+    set rect(radius: 6pt)
+    rect()
+  },
+)
+
+---
+// Test rounding corners without a radius.
+#set rect(width: 30pt, height: 24pt, stroke: 2pt, rounded: true)
+#stack(
+  dir: ltr,
+  spacing: 5pt,
+  rect(),
+  rect(radius: (top-left: 12pt)),
+  square(size: 24pt, stroke: 2pt, rounded: true),
+)
+#test(rect(rounded: false).rounded, false)
+
+---
+// Test that dashes run continuously around rounded corners.
+#set rect(width: 50pt, height: 30pt, radius: 10pt)
+#stack(
+  dir: ltr,
+  spacing: 6pt,
+  rect(stroke: (thickness: 2pt, dash: "dashed")),
+  rect(stroke: (thickness: 1pt, dash: "dotted", cap: "round"), radius: 50%),
+)
+#box(
+  width: 100%,
+  inset: 6pt,
+  radius: 6pt,
+  stroke: (thickness: 1pt, dash: "dash-dotted"),
+)[Dashed box]
@@ -0,0 +1,24 @@
+// Test drop shadows on shapes.
+
+---
+#set page(width: 160pt)
+#set rect(fill: white, stroke: 1pt, width: 30pt, height: 20pt)
+#stack(
+  dir: ltr,
+  spacing: 10pt,
+  rect(shadow: (dx: 3pt, dy: 3pt, color: gray)),
+  rect(radius: 5pt, shadow: (dx: 2pt, dy: 2pt, blur: 6pt)),
+  circle(radius: 10pt, fill: yellow, shadow: (dy: 3pt, blur: 4pt, color: rgb(0, 0, 255, 50%))),
+)
+
+---
+// Translucent hard shadow.
+#box(square(size: 20pt, fill: red, shadow: (dx: 10pt, dy: 10pt, color: rgb(0, 0, 0, 50%))))
+
+---
+// Test the shadow field.
+#test(rect(shadow: (dx: 2pt)).shadow, (color: black, dx: 2pt, dy: 0pt, blur: 0pt))
+
+---
+// Error: 15-23 unexpected key "x", valid keys are "color", "dx", "dy", and "blur"
+#rect(shadow: (x: 2pt))
@@ -0,0 +1,43 @@
+// Test smoothing rounded corners into superellipses.
+
+---
+#set page(width: 220pt)
+#set rect(width: 40pt, height: 40pt, radius: 20pt, fill: teal, stroke: 2pt + navy)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(),
+  rect(smoothing: 0.5),
+  rect(smoothing: 1.0),
+  square(size: 40pt, radius: 10pt, smoothing: 1.0, fill: teal),
+)
+
+---
+// Smoothing also applies to clipping, shadows, and per-side strokes.
+#set page(width: 220pt)
+#stack(
+  dir: ltr,
+  spacing: 12pt,
+  rect(
+    width: 50pt,
+    height: 40pt,
+    radius: 16pt,
+    smoothing: 1.0,
+    clip: true,
+    inset: 0pt,
+    rect(width: 100%, height: 100%, fill: gradient.linear(red, blue)),
+  ),
+  rect(
+    width: 50pt,
+    height: 40pt,
+    radius: (x: 20pt, y: 10pt),
+    smoothing: 1.0,
+    stroke: (left: 3pt + red, top: 3pt + green, rest: 3pt + blue),
+    fill: white,
+    shadow: (dx: 2pt, dy: 2pt, blur: 2pt, color: gray),
+  ),
+)
+
+---
+// Error: 2-22 smoothing must be between 0 and 1
+#rect(smoothing: 1.5)
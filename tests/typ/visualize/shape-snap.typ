@@ -0,0 +1,46 @@
+// Test snapping the height of shapes to a baseline grid.
+
+---
+// Shapes snap to the distance between the baselines of two lines. Here, a
+// line is 10pt high from its top to its bottom edge.
+#set page(width: 160pt)
+#set text(size: 10pt, top-edge: 8pt, bottom-edge: -2pt)
+#set par(leading: 5pt)
+#set rect(inset: 0pt, snap-height: true)
+#rect(width: 100%, height: 2pt, fill: aqua)
+#rect(width: 100%, height: 15pt, fill: eastern)
+#rect(width: 100%, height: 16pt, fill: aqua)
+#rect(width: 100%, fill: eastern, inset: 2pt, align(horizon)[Centered])
+
+#style(styles => {
+  test(measure(rect(height: 2pt), styles).height, 15pt)
+  test(measure(rect(height: 15pt), styles).height, 15pt)
+  test(measure(rect(height: 16pt), styles).height, 30pt)
+  test(measure(square(size: 16pt, snap-height: true), styles), (width: 30pt, height: 30pt))
+})
+
+---
+// The grid unit is the pitch of the lines of a paragraph with the default
+// text edges, too.
+#style(styles => {
+  let one = measure(block[A], styles).height
+  let two = measure(block[A \ A], styles).height
+  let snapped = measure(rect(height: 1pt, snap-height: true), styles).height
+  test(calc.abs((snapped - (two - one)).pt()) < 1e-6, true)
+})
+
+---
+// An explicit unit and turning snapping off again.
+#set page(width: 160pt)
+#style(styles => {
+  test(measure(ellipse(height: 11pt, snap-height: 10pt), styles).height, 20pt)
+  test(measure(circle(radius: 3pt, snap-height: 4pt), styles).width, 8pt)
+  test(measure(rect(height: 11pt, snap-height: false), styles).height, 11pt)
+})
+#stack(
+  dir: ltr,
+  spacing: 4pt,
+  ellipse(width: 30pt, height: 11pt, snap-height: 10pt, fill: olive),
+  circle(radius: 3pt, snap-height: 8pt, fill: maroon),
+  rect(height: 11pt, snap-height: false, fill: blue),
+)
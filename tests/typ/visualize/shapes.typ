@@ -0,0 +1,34 @@
+// Test drawing many shapes at once.
+
+---
+#set page(width: 120pt)
+#let points = range(12).map(i => (i * 6pt, calc.rem(i * 7, 5) * 6pt))
+#shapes(
+  fill: blue,
+  ..points.map(((x, y)) => (x: x, y: y, width: 4pt, height: 4pt, kind: "ellipse")),
+  (x: 0pt, y: 30pt, width: 70pt, height: 0.5pt, fill: gray),
+  (x: 75pt, y: 0pt, width: 10pt, height: 10pt, fill: none),
+  (x: 75pt, y: 15pt, width: 10pt, height: 1em, stroke: 2pt + red),
+)
+
+---
+// The element is sized to fit its shapes.
+#set page(width: 120pt)
+#shapes(stroke: 1pt, (x: 10pt, y: 5pt, width: 20pt, height: 10pt))
+#style(styles => {
+  let size = measure(shapes((x: 10pt, y: 5pt, width: 20pt, height: 10pt)), styles)
+  test(size, (width: 30pt, height: 15pt))
+  test(measure(shapes(), styles), (width: 0pt, height: 0pt))
+})
+
+---
+// Error: 9-22 shape is missing a height
+#shapes((width: 10pt))
+
+---
+// Error: 9-50 expected "rect" or "ellipse"
+#shapes((width: 10pt, height: 10pt, kind: "star"))
+
+---
+// Error: 9-49 unexpected key "radius", valid keys are "kind", "x", "y", "width", "height", "fill", and "stroke"
+#shapes((width: 10pt, height: 10pt, radius: 2pt))
@@ -56,9 +56,29 @@
     (0pt, 20pt), (15pt, 0pt), (0pt, 40pt), (15pt, 45pt)),
 )
 ---
-// Error: 29-56 unexpected key "thicknes", valid keys are "paint", "thickness", "cap", "join", "dash", and "miter-limit"
+// Error: 29-56 unexpected key "thicknes", valid keys are "paint", "thickness", "cap", "join", "dash", "miter-limit", "align", and "hairline"
 #line(length: 60pt, stroke: (paint: red, thicknes: 1pt))
 
+---
+// Error: 15-46 expected "inside", "center", "outside", or auto
+#rect(stroke: (thickness: 2pt, align: "left"))
+
+---
+// Error: 29-59 miter limit must be at least 1.0
+#line(length: 60pt, stroke: (paint: red, miter-limit: 0.5))
+
+---
+// Error: 29-64 miter limit must be finite
+#line(length: 60pt, stroke: (paint: red, miter-limit: calc.nan))
+
+---
+// Error: 49-52 miter limit must be at least 1.0
+#line(length: 60pt, stroke: stroke(miter-limit: 0.5))
+
+---
+// Error: 49-57 miter limit must be finite
+#line(length: 60pt, stroke: stroke(miter-limit: calc.inf))
+
 ---
 // Error: 29-55 expected "solid", "dotted", "densely-dotted", "loosely-dotted", "dashed", "densely-dashed", "loosely-dashed", "dash-dotted", "densely-dash-dotted", "loosely-dash-dotted", array, dictionary, none, or auto
 #line(length: 60pt, stroke: (paint: red, dash: "dash"))
@@ -96,6 +116,21 @@
   ((50%, 0%), (4%, 4%)),
 )
 
+---
+// Test that a dash phase aligns the dashes of adjacent lines.
+#let dashed(phase) = (thickness: 2pt, dash: (array: (4pt, 2pt), phase: phase))
+#stack(
+  dir: ltr,
+  line(length: 20pt, stroke: dashed(0pt)),
+  line(length: 20pt, stroke: dashed(0pt)),
+)
+#stack(
+  dir: ltr,
+  line(length: 20pt, stroke: dashed(0pt)),
+  line(length: 20pt, stroke: dashed(20pt)),
+)
+#line(length: 40pt, stroke: dashed(0pt))
+
 ---
 // Converting to stroke
 #assert.eq(stroke(red).paint, red)
@@ -105,7 +140,7 @@
 #assert.eq(stroke((cap: auto, paint: blue)).cap, auto)
 #assert.eq(stroke((cap: auto, paint: blue)).thickness, auto)
 
-// Error: 9-21 unexpected key "foo", valid keys are "paint", "thickness", "cap", "join", "dash", and "miter-limit"
+// Error: 9-21 unexpected key "foo", valid keys are "paint", "thickness", "cap", "join", "dash", "miter-limit", "align", and "hairline"
 #stroke((foo: "bar"))
 
 // Constructing with named arguments
@@ -113,3 +148,175 @@
 #assert.eq(stroke(thickness: 2pt), stroke(2pt))
 #assert.eq(stroke(cap: "round").thickness, auto)
 #assert.eq(stroke(cap: "round", thickness: auto).thickness, auto)
+
+---
+// Test thicknesses relative to the shape's shorter side.
+#set page(width: 200pt)
+#set rect(stroke: (thickness: 5%))
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(width: 20pt, height: 20pt),
+  rect(width: 60pt, height: 40pt),
+  circle(radius: 20pt, stroke: (thickness: 10% + 1pt, paint: blue)),
+  square(size: 20pt, stroke: (left: (thickness: 20%), rest: 1pt)),
+)
+#test(stroke((thickness: 5%)).thickness, 5%)
+#test(stroke((thickness: 5% + 1pt)).thickness, 5% + 1pt)
+#test(stroke(2pt).thickness, 2pt)
+#test(repr(stroke((paint: red, thickness: 5%))), "5% + rgb(\"#ff4136\")")
+
+---
+// A relative thickness is inherited along with its absolute part.
+#set rect(width: 20pt, height: 20pt, stroke: (thickness: 25%))
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(stroke: blue),
+  rect(stroke: 1pt),
+  rect(stroke: (thickness: 1pt + 10%)),
+)
+
+---
+// Error: 2-31 stroke thickness must be absolute
+// Hint: 2-31 only rectangles, squares, ellipses, and circles support relative stroke thicknesses
+#line(stroke: (thickness: 5%))
+
+---
+// Error: 2-36 stroke thickness must be absolute
+// Hint: 2-36 only rectangles, squares, ellipses, and circles support relative stroke thicknesses
+#box(stroke: (thickness: 1pt + 5%))
+
+---
+// Error: 2-43 stroke thickness must be absolute
+// Hint: 2-43 only rectangles, squares, ellipses, and circles support relative stroke thicknesses
+#underline(stroke: (thickness: 5%))[Hello]
+
+---
+// Test that hairline strokes are exactly one pixel wide, regardless of their
+// thickness.
+#set line(length: 100%)
+#line(stroke: 0.05pt)
+#line(stroke: (thickness: 0.05pt, hairline: true))
+#line(stroke: (thickness: 4pt, hairline: true))
+#rect(width: 100%, stroke: (thickness: 0.05pt, paint: blue, hairline: true))
+#test(stroke((thickness: 0.05pt, hairline: true)).hairline, true)
+#test(stroke(hairline: true).thickness, auto)
+#test(stroke(1pt).hairline, auto)
+#test(repr(stroke((paint: red, hairline: true))), "(paint: rgb(\"#ff4136\"), hairline: true)")
+
+---
+// Test strokes inside of, centered on, and outside of the outline. The red
+// box marks the shape's frame.
+#set page(width: 200pt)
+#let framed(shape) = box(stroke: 0.5pt + red, shape)
+#let strokes = ("inside", "center", "outside").map(align => (
+  thickness: 6pt,
+  paint: rgb(0, 116, 217, 60%),
+  align: align,
+))
+#stack(
+  dir: ltr,
+  spacing: 16pt,
+  ..strokes.map(stroke => framed(rect(width: 40pt, height: 30pt, stroke: stroke))),
+)
+#v(8pt)
+#stack(
+  dir: ltr,
+  spacing: 16pt,
+  ..strokes.map(stroke => framed(circle(radius: 15pt, stroke: stroke))),
+)
+
+---
+// The fill, clip, and links stay at the boundary when the stroke moves. The
+// translucent stroke shows where it overlaps the fill.
+#set page(width: 200pt)
+#let strokes = ("inside", "center", "outside").map(align => (
+  thickness: 6pt,
+  paint: rgb(0, 116, 217, 60%),
+  align: align,
+))
+#stack(
+  dir: ltr,
+  spacing: 16pt,
+  ..strokes.map(stroke => rect(
+    width: 40pt,
+    height: 30pt,
+    fill: yellow,
+    stroke: stroke,
+    clip: true,
+    inset: 0pt,
+    square(size: 40pt, fill: red),
+  )),
+)
+
+---
+// Test that predefined dash patterns scale with the thickness, while explicit
+// lengths stay fixed and numbers are multiples of the thickness.
+#set page(width: 120pt)
+#set line(length: 100%)
+#for thickness in (0.5pt, 1pt, 2pt, 4pt) {
+  line(stroke: (thickness: thickness, dash: "dash-dotted"))
+}
+#line(stroke: (thickness: 4pt, dash: (3pt, 3pt)))
+#line(stroke: (thickness: 4pt, dash: (3, 1, "dot", 1)))
+#test(stroke(dash: (2, "dot", 2pt)).dash, (array: (2.0, "dot", 2pt), phase: 0pt))
+
+---
+// Test the shorthands for a stroke with only a thickness or only a paint,
+// which leave the other field at its default.
+#test(rect(stroke: 2pt).stroke, stroke(2pt))
+#test(rect(stroke: red).stroke, stroke(red))
+#test(rect(stroke: (paint: red, thickness: 2pt)).stroke, stroke(2pt + red))
+#test(stroke(2pt).paint, auto)
+#test(stroke(red).thickness, auto)
+
+// Set rules fold the shorthands field by field.
+#set page(width: 160pt)
+#set rect(width: 30pt, height: 20pt)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(stroke: 2pt),
+  rect(stroke: red),
+  rect(stroke: (paint: red, thickness: 2pt)),
+  {
+    set rect(stroke: 2pt)
+    rect(stroke: red)
+  },
+)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  line(length: 30pt, stroke: 2pt),
+  line(length: 30pt, stroke: red),
+  circle(radius: 10pt, stroke: 2pt),
+  ellipse(width: 30pt, height: 20pt, stroke: red),
+)
+
+---
+// Test a different dash pattern for each side. Each side folds its own
+// stroke, so the dash is independent of the other sides.
+#set page(width: 160pt)
+#set rect(width: 40pt, height: 30pt, stroke: 2pt)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(stroke: (top: (dash: "solid"), bottom: (dash: "dashed"))),
+  rect(stroke: (x: 2pt + blue, bottom: (paint: red, dash: "dotted"))),
+  rect(radius: 6pt, stroke: (left: (dash: "dashed"), rest: (dash: "solid"))),
+)
+
+---
+// Test a dashed rounded box and dash patterns without gaps, which are drawn
+// as solid strokes.
+#set page(width: 160pt)
+#set rect(width: 40pt, height: 20pt, radius: 5pt)
+#let dashed(dash) = (thickness: 2pt, cap: "round", join: "bevel", miter-limit: 2, dash: dash)
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(stroke: dashed((array: (4pt, 2pt), phase: 1pt))),
+  rect(stroke: dashed("solid")),
+  rect(stroke: dashed((array: (0pt, 0pt), phase: 1pt))),
+)
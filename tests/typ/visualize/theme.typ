@@ -0,0 +1,38 @@
+// Test paints of a theme.
+
+---
+#set page(width: 160pt)
+#set theme(paints: (primary: eastern, accent: maroon))
+#let primary = theme.paint("primary")
+#stack(
+  dir: ltr,
+  spacing: 8pt,
+  rect(fill: primary)[A],
+  square(fill: theme.paint("accent"), size: 20pt),
+  {
+    set theme(paints: (primary: olive))
+    ellipse(fill: primary, width: 30pt, height: 20pt)
+  },
+  circle(fill: primary, radius: 10pt),
+)
+
+---
+// Fill functions can return tokens and tokens keep their name.
+#set theme(paints: (base: blue))
+#rect(fill: size => theme.paint("base"))[Function]
+#test(repr(theme.paint("base")), "theme.paint(\"base\")")
+#test(rect(fill: theme.paint("base")).fill, theme.paint("base"))
+
+---
+// Error: 2-36 theme does not define a paint named `missing`
+#rect(fill: theme.paint("missing"))
+
+---
+// Strokes only take literal paints.
+// Error: 15-31 expected length, color, gradient, pattern, dictionary, stroke, none, or auto, found theme paint
+#rect(stroke: theme.paint("a"))
+
+---
+// So do the layers of a fill.
+// Error: 13-50 expected color, gradient, or pattern, found theme paint
+#rect(fill: (theme.paint("a"), rgb(0, 0, 0, 50%)))